@@ -20,12 +20,13 @@ pub trait BacaApi {
         task_id: &str,
     ) -> Result<Results>;
     fn get_tasks(&self, connection_config: &ConnectionConfig) -> Result<Tasks>;
+    /// Sends the file and returns the id of the created submit.
     fn submit(
         &self,
         connection_config: &ConnectionConfig,
         task: &Task,
         file_path: &str,
-    ) -> Result<()>;
+    ) -> Result<String>;
     fn get_allowed_language(
         &self,
         connection_config: &ConnectionConfig,
@@ -2,13 +2,20 @@ use crate::api::baca_api::BacaApi;
 use crate::api::details::EMPTY_RESPONSE;
 use crate::api::Request;
 use crate::error::{Error, Result};
+use crate::log;
 use crate::model::{Language, Results, Submit, Task, Tasks};
 use crate::parse::from_baca_output::FromBacaOutput;
 use crate::workspace::ConnectionConfig;
 use reqwest::blocking::Response;
+use std::fs;
 use std::str::FromStr;
+use std::thread;
+use std::time::Duration;
 use tracing::{debug, info};
 
+const SUBMIT_POLL_ATTEMPTS: u32 = 10;
+const SUBMIT_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
 #[derive(Default)]
 pub struct BacaService {}
 
@@ -27,29 +34,13 @@ impl BacaApi for BacaService {
     ) -> Result<Submit> {
         let resp = Request::new(connection_config).details(submit_id)?;
         check_response_status(&resp)?;
-        let resp = resp.text()?;
-        debug!("Received raw submit: {}", resp);
-
-        if resp.contains("failed") {
-            return Err(Error::InvalidSubmitId);
-        }
-
-        Ok(Submit::parse(
-            connection_config,
-            &check_for_empty_response(resp)?,
-        ))
+        parse_submit_details(connection_config, resp.text()?)
     }
 
     fn get_results(&self, connection_config: &ConnectionConfig) -> Result<Results> {
         let resp = Request::new(connection_config).results()?;
         check_response_status(&resp)?;
-        let resp = resp.text().expect("Invalid submit data");
-        debug!("Received raw results: {}", resp);
-
-        Ok(Results::from_baca_output(
-            connection_config,
-            &check_for_empty_response(resp)?,
-        ))
+        parse_results(connection_config, resp.text().expect("Invalid submit data"))
     }
 
     fn get_results_by_task(
@@ -57,22 +48,13 @@ impl BacaApi for BacaService {
         connection_config: &ConnectionConfig,
         task_id: &str,
     ) -> Result<Results> {
-        let tasks = self.get_tasks(connection_config)?;
-        let task = tasks.get_by_id(task_id)?;
-        info!("Showing logs for task {}", &task.problem_name);
-        Ok(self
-            .get_results(connection_config)?
-            .filter_by_task(&task.problem_name))
+        results_by_task(self, connection_config, task_id)
     }
 
     fn get_tasks(&self, connection_config: &ConnectionConfig) -> Result<Tasks> {
         let resp = Request::new(connection_config).tasks()?;
         check_response_status(&resp)?;
-
-        let resp = resp.text().expect("Invalid submit data");
-        debug!("Received raw tasks: {}", resp);
-
-        Tasks::from_str(&check_for_empty_response(resp)?)
+        parse_tasks(resp.text().expect("Invalid submit data"))
     }
 
     fn submit(
@@ -80,16 +62,24 @@ impl BacaApi for BacaService {
         connection_config: &ConnectionConfig,
         task: &Task,
         file_path: &str,
-    ) -> Result<()> {
+    ) -> Result<String> {
         debug!("{:?}", task);
+        let size = fs::metadata(file_path)?.len();
+        let before = self.get_results_by_task(connection_config, &task.id)?;
+
         let resp = Request::new(connection_config).submit(task, file_path)?;
         let resp = resp.text()?;
-        debug!("Response: {}", resp);
 
-        match resp.as_str() {
-            "Niezalogowany jesteś" => Err(Error::LoggedOut),
-            "Błąd" => Err(Error::TaskNotActive),
-            _ => Ok(()),
+        match parse_submit_response(&resp)? {
+            Some(id) => Ok(id),
+            None => wait_for_submit(
+                self,
+                connection_config,
+                task,
+                size,
+                &before,
+                SUBMIT_POLL_INTERVAL,
+            ),
         }
     }
 
@@ -100,18 +90,111 @@ impl BacaApi for BacaService {
     ) -> Result<Option<Language>> {
         let response = Request::new(connection_config).allowed_languages(task_id)?;
         check_response_status(&response)?;
-        let response = response.text()?;
-        debug!("Received raw allowed languages: {:?}", response);
-        Ok(Option::<Language>::from_baca_output(
-            connection_config,
-            &response,
-        ))
+        Ok(parse_allowed_language(connection_config, &response.text()?))
+    }
+}
+
+pub(crate) fn parse_submit_details(
+    connection_config: &ConnectionConfig,
+    resp: String,
+) -> Result<Submit> {
+    debug!("Received raw submit: {}", resp);
+
+    if resp.contains("failed") {
+        return Err(Error::InvalidSubmitId);
+    }
+
+    Ok(Submit::parse(
+        connection_config,
+        &check_for_empty_response(resp)?,
+    ))
+}
+
+pub(crate) fn parse_results(connection_config: &ConnectionConfig, resp: String) -> Result<Results> {
+    debug!("Received raw results: {}", resp);
+
+    Ok(Results::from_baca_output(
+        connection_config,
+        &check_for_empty_response(resp)?,
+    ))
+}
+
+pub(crate) fn parse_tasks(resp: String) -> Result<Tasks> {
+    debug!("Received raw tasks: {}", resp);
+
+    Tasks::from_str(&check_for_empty_response(resp)?)
+}
+
+pub(crate) fn parse_allowed_language(
+    connection_config: &ConnectionConfig,
+    resp: &str,
+) -> Option<Language> {
+    debug!("Received raw allowed languages: {:?}", resp);
+
+    Option::<Language>::from_baca_output(connection_config, resp)
+}
+
+pub(crate) fn results_by_task<A: BacaApi>(
+    api: &A,
+    connection_config: &ConnectionConfig,
+    task_id: &str,
+) -> Result<Results> {
+    let tasks = api.get_tasks(connection_config)?;
+    let task = tasks.get_by_id(task_id)?;
+    info!("Showing logs for task {}", &task.problem_name);
+    Ok(api
+        .get_results(connection_config)?
+        .filter_by_task(&task.problem_name))
+}
+
+/// Fails on error pages, returns the submit id when BaCa sends it.
+pub(crate) fn parse_submit_response(resp: &str) -> Result<Option<String>> {
+    debug!("Response: {}", resp);
+
+    match resp {
+        "Niezalogowany jesteś" => return Err(Error::LoggedOut),
+        "Błąd" => return Err(Error::TaskNotActive),
+        _ => {}
+    }
+
+    let id = parse_submit_id(resp);
+    if let Some(id) = &id {
+        info!("Submit id from response: {}", id);
     }
+
+    Ok(id)
+}
+
+/// Polls the results until a new submit of the task shows up.
+pub(crate) fn wait_for_submit<A: BacaApi>(
+    api: &A,
+    connection_config: &ConnectionConfig,
+    task: &Task,
+    size: u64,
+    before: &Results,
+    interval: Duration,
+) -> Result<String> {
+    for attempt in 1..=SUBMIT_POLL_ATTEMPTS {
+        let after = api.get_results_by_task(connection_config, &task.id)?;
+
+        if let Some(id) = find_new_submit(before, &after, task, size) {
+            info!("Submit id found after {} attempts: {}", attempt, id);
+            return Ok(id);
+        }
+
+        thread::sleep(interval);
+    }
+
+    Err(Error::SubmitNotFound)
 }
 
 fn log_response_details(login_response: &Response) {
     for (name, val) in login_response.headers() {
-        debug!("Response header: {} = {:?}", name, val);
+        debug!(
+            "Response header: {} = {}",
+            name,
+            log::redact(&format!("{:?}", val))
+        );
     }
 
     debug!("Status code: {}", login_response.status());
@@ -123,7 +206,12 @@ fn extract_cookie(response: &Response) -> Result<String> {
         .next()
         .ok_or(Error::InvalidLoginOrPassword)?;
 
-    debug!("Cookie: {} = {}", cookie.name(), cookie.value());
+    log::register_secret(cookie.value());
+    debug!(
+        "Cookie: {} = {}",
+        cookie.name(),
+        log::redact(cookie.value())
+    );
     Ok(cookie.value().to_string())
 }
 
@@ -143,6 +231,44 @@ fn check_for_empty_response(resp: String) -> Result<String> {
     }
 }
 
+/// BaCa usually answers `sendSubmit` with an empty page, but use the id if there is one.
+fn parse_submit_id(resp: &str) -> Option<String> {
+    let resp = resp.trim();
+
+    match !resp.is_empty() && resp.chars().all(|x| x.is_ascii_digit()) {
+        true => Some(resp.to_string()),
+        false => None,
+    }
+}
+
+/// Finds the submit created since `before` was fetched, by its id, task, language and timestamp.
+///
+/// Of several new submits, the one of the sent size is preferred. Any other is still returned,
+/// so a damaged upload is found and reported by the caller.
+/// Timestamps are compared with the newest earlier submit, as they come from the server clock.
+fn find_new_submit(before: &Results, after: &Results, task: &Task, size: u64) -> Option<String> {
+    let sent_after = before
+        .submits
+        .iter()
+        .map(|x| x.timestamp.as_str())
+        .max()
+        .unwrap_or_default();
+
+    let candidates = after
+        .submits
+        .iter()
+        .filter(|x| !before.submits.iter().any(|old| old.id == x.id))
+        .filter(|x| x.timestamp.as_str() >= sent_after)
+        .filter(|x| x.has_language(task.language))
+        .collect::<Vec<_>>();
+
+    candidates
+        .iter()
+        .find(|x| u64::try_from(x.size).is_ok_and(|x| x == size))
+        .or(candidates.first())
+        .map(|x| x.id.clone())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -155,8 +281,11 @@ mod tests {
             host: "mn2020".to_string(),
             login: "login".to_string(),
             password: "pass".to_string(),
+            password_command: None,
             permutation: api::details::permutation(),
             cookie: "invalid".to_string(),
+            secrets: None,
+            profile: None,
         }
     }
 
@@ -165,8 +294,11 @@ mod tests {
             host: "invalid".to_string(),
             login: "login".to_string(),
             password: "pass".to_string(),
+            password_command: None,
             permutation: "invalid".to_string(),
             cookie: "".to_string(),
+            secrets: None,
+            profile: None,
         }
     }
 
@@ -325,4 +457,86 @@ mod tests {
 
         assert!(result.is_none());
     }
+
+    fn make_submit(id: &str, timestamp: &str, size: i32, language: &str) -> Submit {
+        Submit {
+            id: id.to_string(),
+            timestamp: timestamp.to_string(),
+            size,
+            language: language.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn submit_id_should_be_parsed_only_from_numeric_response() {
+        assert_eq!(parse_submit_id(" 4334\n"), Some("4334".to_string()));
+        assert_eq!(parse_submit_id(""), None);
+        assert_eq!(parse_submit_id("<html></html>"), None);
+    }
+
+    #[test]
+    fn new_submit_should_match_language_regardless_of_size() {
+        let task = Task::new("1", Language::Cpp, "[A] Zera funkcji", 12);
+        let before = Results::new(vec![make_submit("10", "2020-05-17 16:53:41", 100, "C++")]);
+        let after = Results::new(vec![
+            make_submit("12", "2020-05-17 18:53:12", 100, "Java"),
+            make_submit("11", "2020-05-17 18:53:09", 37, "C++"),
+            make_submit("10", "2020-05-17 16:53:41", 100, "C++"),
+        ]);
+
+        assert_eq!(
+            find_new_submit(&before, &after, &task, 100),
+            Some("11".to_string())
+        );
+    }
+
+    #[test]
+    fn new_submit_of_sent_size_should_be_preferred() {
+        let task = Task::new("1", Language::Cpp, "[A] Zera funkcji", 12);
+        let before = Results::new(vec![make_submit("10", "2020-05-17 16:53:41", 100, "C++")]);
+        let after = Results::new(vec![
+            make_submit("12", "2020-05-17 18:53:10", 37, "C++"),
+            make_submit("11", "2020-05-17 18:53:09", 100, "C++"),
+            make_submit("10", "2020-05-17 16:53:41", 100, "C++"),
+        ]);
+
+        assert_eq!(
+            find_new_submit(&before, &after, &task, 100),
+            Some("11".to_string())
+        );
+        assert_eq!(
+            find_new_submit(&before, &after, &task, 55),
+            Some("12".to_string())
+        );
+    }
+
+    #[test]
+    fn new_submit_should_match_language_reported_in_polish() {
+        let task = Task::new("1", Language::CppWithFileSupport, "[A] Zera funkcji", 12);
+        let before = Results::new(vec![]);
+        let after = Results::new(vec![make_submit(
+            "11",
+            "2020-05-17 18:53:09",
+            100,
+            "C++ z obsluga plikow",
+        )]);
+
+        assert_eq!(
+            find_new_submit(&before, &after, &task, 100),
+            Some("11".to_string())
+        );
+    }
+
+    #[test]
+    fn submits_older_than_known_ones_should_not_match() {
+        let task = Task::new("1", Language::Cpp, "[A] Zera funkcji", 12);
+        let before = Results::new(vec![make_submit("10", "2020-05-17 16:53:41", 100, "C++")]);
+        let after = Results::new(vec![
+            make_submit("10", "2020-05-17 16:53:41", 100, "C++"),
+            make_submit("9", "2020-05-16 10:00:00", 100, "C++"),
+        ]);
+
+        assert_eq!(find_new_submit(&before, &after, &task, 100), None);
+    }
 }
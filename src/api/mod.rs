@@ -4,5 +4,7 @@ pub use self::request_type::RequestType;
 pub mod baca_api;
 pub mod baca_service;
 pub mod details;
+pub mod replay_service;
 mod request;
 mod request_type;
+pub mod traffic;
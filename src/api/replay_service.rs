@@ -0,0 +1,212 @@
+use crate::api::baca_api::BacaApi;
+use crate::api::baca_service::{
+    parse_allowed_language, parse_results, parse_submit_details, parse_submit_response,
+    parse_tasks, results_by_task, wait_for_submit,
+};
+use crate::api::traffic;
+use crate::api::traffic::Exchange;
+use crate::error::{Error, Result};
+use crate::model::{Language, Results, Submit, Task, Tasks};
+use crate::workspace::ConnectionConfig;
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::Duration;
+use tracing::{debug, info};
+
+/// Serves the traffic recorded with `BACA_RECORD` instead of connecting to BaCa.
+///
+/// Responses to the same request are served in the recorded order, repeating the last one.
+/// Submit details and allowed languages are matched by their id.
+pub struct ReplayService {
+    exchanges: Vec<Exchange>,
+    served: Mutex<HashMap<String, usize>>,
+}
+
+impl ReplayService {
+    pub fn new(dir: &Path) -> Result<Self> {
+        let exchanges = traffic::read_all(dir)?;
+        info!("Replaying {} exchanges from {:?}.", exchanges.len(), dir);
+
+        Ok(Self {
+            exchanges,
+            served: Mutex::new(HashMap::new()),
+        })
+    }
+
+    fn next(&self, request: &str) -> Result<String> {
+        let recorded = self
+            .exchanges
+            .iter()
+            .filter(|x| x.request == request)
+            .collect::<Vec<_>>();
+        let mut served = self.served.lock().unwrap();
+        let count = served.entry(request.to_string()).or_default();
+
+        let exchange = recorded
+            .get(*count)
+            .or(recorded.last())
+            .ok_or_else(|| Error::NotRecorded(request.to_string()))?;
+        *count += 1;
+
+        serve(exchange)
+    }
+
+    fn find(&self, request: &str, id: &str) -> Result<String> {
+        let suffix = format!("|{}|", id);
+        let exchange = self
+            .exchanges
+            .iter()
+            .rev()
+            .find(|x| x.request == request && x.payload.ends_with(&suffix))
+            .ok_or_else(|| Error::NotRecorded(format!("{} {}", request, id)))?;
+
+        serve(exchange)
+    }
+}
+
+fn serve(exchange: &Exchange) -> Result<String> {
+    debug!(
+        "Replaying {} from {}.",
+        exchange.request, exchange.timestamp
+    );
+
+    if exchange.status == 404 {
+        return Err(Error::InvalidHost);
+    }
+
+    Ok(exchange.response.clone())
+}
+
+impl BacaApi for ReplayService {
+    fn get_cookie(&self, _connection_config: &ConnectionConfig) -> Result<String> {
+        self.next("login")?;
+        Ok("REPLAYED-SESSION".to_string())
+    }
+
+    fn get_submit_details(
+        &self,
+        connection_config: &ConnectionConfig,
+        submit_id: &str,
+    ) -> Result<Submit> {
+        parse_submit_details(connection_config, self.find("submit_details", submit_id)?)
+    }
+
+    fn get_results(&self, connection_config: &ConnectionConfig) -> Result<Results> {
+        parse_results(connection_config, self.next("results")?)
+    }
+
+    fn get_results_by_task(
+        &self,
+        connection_config: &ConnectionConfig,
+        task_id: &str,
+    ) -> Result<Results> {
+        results_by_task(self, connection_config, task_id)
+    }
+
+    fn get_tasks(&self, _connection_config: &ConnectionConfig) -> Result<Tasks> {
+        parse_tasks(self.next("tasks")?)
+    }
+
+    fn submit(
+        &self,
+        connection_config: &ConnectionConfig,
+        task: &Task,
+        file_path: &str,
+    ) -> Result<String> {
+        let size = fs::metadata(file_path)?.len();
+        let before = self.get_results_by_task(connection_config, &task.id)?;
+
+        match parse_submit_response(&self.next("submit")?)? {
+            Some(id) => Ok(id),
+            None => wait_for_submit(self, connection_config, task, size, &before, Duration::ZERO),
+        }
+    }
+
+    fn get_allowed_language(
+        &self,
+        connection_config: &ConnectionConfig,
+        task_id: &str,
+    ) -> Result<Option<Language>> {
+        let response = self.find("allowed_languages", task_id)?;
+        Ok(parse_allowed_language(connection_config, &response))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert_fs::TempDir;
+    use time::OffsetDateTime;
+
+    const TASKS: &str = r#"//OK[0,12,11,10,3,3,9,8,7,3,3,6,5,4,3,3,2,2,1,["testerka.gwt.client.tools.DataSource/1474249525","[[Ljava.lang.String;/4182515373","[Ljava.lang.String;/2600011424","1","Metoda parametryzacji","12","2","Metoda parametryzacji torusów","4","id","nazwa","liczba OK"],0,7]"#;
+    const DETAILS: &str = r#"//OK[0,12,50,2,5,7,49,2,5,7,48,2,5,7,47,2,5,7,46,2,5,4,4,3,0,45,44,43,42,41,40,39,38,8,5,7,37,36,36,35,34,33,32,8,5,1,4,3,31,0,30,29,28,27,26,25,24,23,22,9,5,21,20,19,18,17,16,15,14,13,9,5,1,4,3,0,0,12,11,2,5,7,10,2,5,7,9,2,5,7,8,2,5,7,6,2,5,4,4,3,2,1,["testerka.gwt.client.submits.SubmitDetailsModel/2564112456","logs","testerka.gwt.client.tools.DataSource/1474249525","[[Ljava.lang.String;/4182515373","[Ljava.lang.String;/2600011424","532","program zaakceptowany","536","564","572","czas","status","[G] Funkcje sklejane","4","2020-05-13 07:39:59","2020-06-04 23:00:00","2020-06-15 23:00:00","122","30","1024","125","Nazwa zdania","Liczba punktow do zdobycia","Start zadania","Termin oddania","Koniec zdania","Limit pamieci (MB)","Limit czasu kompilacji (s)","Limit pamieci na kompilacje (MB)","Limit kodu zrodlowego (kB)","code","4334","C++","2020-05-17 18:53:09","1190","100","4.00","id","język","czas zgłoszenia","rozmiar (b)","zaliczone (%)","spoznienie (%)","punkty","nazwa statusu","test0/0","test1/0","test2/0","test3/0","test"],0,7]"#;
+
+    fn record(dir: &Path, exchanges: &[(&str, &str, u16, &str)]) {
+        fs::create_dir_all(dir).unwrap();
+
+        for (i, (request, payload, status, response)) in exchanges.iter().enumerate() {
+            let mut exchange = Exchange::new(request, payload, *status, response);
+            exchange.timestamp =
+                OffsetDateTime::from_unix_timestamp(1_700_000_000 + i as i64).unwrap();
+            fs::write(
+                dir.join(format!("{}-{}.yaml", i, request)),
+                serde_yaml::to_string(&exchange).unwrap(),
+            )
+            .unwrap();
+        }
+    }
+
+    #[test]
+    fn recorded_responses_should_be_parsed() {
+        let dir = TempDir::new().unwrap();
+        record(
+            dir.path(),
+            &[
+                ("tasks", "7|0|4|...|", 200, TASKS),
+                (
+                    "submit_details",
+                    "7|0|5|...|I|1|2|3|4|1|5|4334|",
+                    200,
+                    DETAILS,
+                ),
+            ],
+        );
+        let config = ConnectionConfig::default();
+
+        let replay = ReplayService::new(dir.path()).unwrap();
+
+        let tasks = replay.get_tasks(&config).unwrap();
+        assert_eq!(tasks.tasks.len(), 2);
+        assert_eq!(tasks.tasks[1].problem_name, "Metoda parametryzacji torusów");
+        let submit = replay.get_submit_details(&config, "4334").unwrap();
+        assert_eq!(submit.id, "4334");
+        assert_eq!(submit.problem_name, "[G] Funkcje sklejane");
+        assert!(matches!(
+            replay.get_submit_details(&config, "4335"),
+            Err(Error::NotRecorded(_))
+        ));
+    }
+
+    #[test]
+    fn responses_should_be_served_in_order() {
+        let dir = TempDir::new().unwrap();
+        record(
+            dir.path(),
+            &[
+                ("tasks", "", 200, "//OK[0,[],0,7]"),
+                ("tasks", "", 200, TASKS),
+                ("tasks", "", 404, ""),
+            ],
+        );
+        let config = ConnectionConfig::default();
+
+        let replay = ReplayService::new(dir.path()).unwrap();
+
+        assert!(replay.get_tasks(&config).is_err());
+        assert_eq!(replay.get_tasks(&config).unwrap().tasks.len(), 2);
+        assert!(matches!(replay.get_tasks(&config), Err(Error::InvalidHost)));
+        assert!(matches!(replay.get_tasks(&config), Err(Error::InvalidHost)));
+    }
+}
@@ -2,9 +2,11 @@ use reqwest::blocking::{multipart, RequestBuilder, Response};
 use reqwest::header::{CONTENT_TYPE, COOKIE};
 use tracing::{debug, info};
 
-use crate::api::RequestType;
+use crate::api::traffic::Exchange;
+use crate::api::{traffic, RequestType};
 use crate::error;
 use crate::error::Error;
+use crate::log;
 use crate::model::Task;
 use crate::workspace::ConnectionConfig;
 
@@ -24,35 +26,44 @@ impl<'a> Request<'a> {
         }
     }
 
-    pub fn login(self) -> reqwest::Result<Response> {
-        let (login, pass) = self.connection_config.credentials();
-        let req = self.make_request(RequestType::Login(login, pass));
-        req.send()
+    pub fn login(self) -> error::Result<Response> {
+        let (login, pass) = self.connection_config.credentials()?;
+        self.send(RequestType::Login(login, pass))
+            .map_err(|e| e.into())
     }
 
     pub fn details(self, id: &str) -> reqwest::Result<Response> {
-        let req = self.make_request(RequestType::SubmitDetails(id.to_string()));
-        req.send()
+        self.send(RequestType::SubmitDetails(id.to_string()))
     }
 
     pub fn results(self) -> reqwest::Result<Response> {
-        let req = self.make_request(RequestType::Results);
-        req.send()
+        self.send(RequestType::Results)
     }
 
     pub fn tasks(&self) -> reqwest::Result<Response> {
-        let req = self.make_request(RequestType::Tasks);
-        req.send()
+        self.send(RequestType::Tasks)
     }
 
     pub fn submit(&self, task: &Task, file_path: &str) -> error::Result<Response> {
         let req = self.make_submit_request(task, file_path)?;
-        req.send().map_err(|e| e.into())
+        let payload = format!(
+            "zadanie={}&jezyk={}&zrodla={}",
+            task.id,
+            task.language.code(),
+            file_path
+        );
+        capture("submit", &payload, req.send()?).map_err(|e| e.into())
     }
 
     pub fn allowed_languages(&self, task_id: &str) -> reqwest::Result<Response> {
-        let req = self.make_request(RequestType::AllowedLanguages(task_id.to_string()));
-        req.send()
+        self.send(RequestType::AllowedLanguages(task_id.to_string()))
+    }
+
+    fn send(&self, req_type: RequestType) -> reqwest::Result<Response> {
+        let name = req_type.name();
+        let payload = self.connection_config.make_payload(&req_type);
+        let response = self.make_request(req_type).send()?;
+        capture(name, &payload, response)
     }
 
     fn make_request(&self, req_type: RequestType) -> RequestBuilder {
@@ -64,7 +75,7 @@ impl<'a> Request<'a> {
         let payload = self.connection_config.make_payload(&req_type);
 
         info!("Making request to: {}", post_url);
-        debug!("Request payload: {}", payload);
+        debug!("Request payload: {}", log::redact(&payload));
 
         let req = self.make_base_request(&post_url).body(payload);
         let req = match req_type {
@@ -72,7 +83,7 @@ impl<'a> Request<'a> {
             _ => req.header(COOKIE, self.connection_config.make_cookie()),
         };
 
-        debug!("{:?}", req);
+        debug!("{}", log::redact(&format!("{:?}", req)));
         req
     }
 
@@ -108,11 +119,33 @@ impl<'a> Request<'a> {
             .post(url)
             .multipart(form)
             .header(COOKIE, self.connection_config.make_cookie());
-        debug!("{:?}", req);
+        debug!("{}", log::redact(&format!("{:?}", req)));
         Ok(req)
     }
 }
 
+/// Records the raw exchange when capturing, rebuilding the consumed response.
+fn capture(request: &str, payload: &str, response: Response) -> reqwest::Result<Response> {
+    if !traffic::is_capturing() {
+        return Ok(response);
+    }
+
+    let status = response.status();
+    let headers = response.headers().clone();
+    let body = response.bytes()?;
+    traffic::capture(&Exchange::new(
+        request,
+        payload,
+        status.as_u16(),
+        &String::from_utf8_lossy(&body),
+    ));
+
+    let mut rebuilt = http::Response::new(body);
+    *rebuilt.status_mut() = status;
+    *rebuilt.headers_mut() = headers;
+    Ok(rebuilt.into())
+}
+
 #[cfg(test)]
 mod tests {
     use reqwest::StatusCode;
@@ -124,8 +157,11 @@ mod tests {
             host: "mn2020".to_string(),
             login: "login".to_string(),
             password: "password".to_string(),
+            password_command: None,
             permutation: "5A4AE95C27260DF45F17F9BF027335F6".to_string(),
             cookie: "cookie".to_string(),
+            secrets: None,
+            profile: None,
         }
     }
 
@@ -141,9 +177,10 @@ mod tests {
     fn login_should_connect() {
         let baca = make_connection_config();
         let req = Request::new(&baca);
-        let response = req.login();
+        let response = req.login().unwrap();
 
-        check_response(response);
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.text().unwrap(), "//OK[0,[],0,7]");
     }
 
     #[test]
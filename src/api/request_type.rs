@@ -21,6 +21,17 @@ impl RequestType {
         }
     }
 
+    /// Name of the request in the captured traffic.
+    pub fn name(&self) -> &'static str {
+        match self {
+            RequestType::Results => "results",
+            RequestType::SubmitDetails(_) => "submit_details",
+            RequestType::Login(_, _) => "login",
+            RequestType::Tasks => "tasks",
+            RequestType::AllowedLanguages(_) => "allowed_languages",
+        }
+    }
+
     pub fn mapping(&self) -> String {
         match *self {
             RequestType::Results => "submits".to_string(),
@@ -0,0 +1,152 @@
+use crate::error::Result;
+use crate::log;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use time::OffsetDateTime;
+use tracing::{debug, error};
+
+/// Number of exchanges kept in the capture directory.
+pub const KEEP: usize = 20;
+
+/// Directory receiving the exchanges, set at trace level.
+static CAPTURE_DIR: Mutex<Option<PathBuf>> = Mutex::new(None);
+
+/// Directory receiving all exchanges, set by `BACA_RECORD`.
+static RECORD_DIR: Mutex<Option<PathBuf>> = Mutex::new(None);
+
+/// A raw request with the server response, secrets redacted.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Exchange {
+    #[serde(with = "time::serde::rfc3339")]
+    pub timestamp: OffsetDateTime,
+    pub request: String,
+    pub payload: String,
+    pub status: u16,
+    pub response: String,
+}
+
+impl Exchange {
+    pub fn new(request: &str, payload: &str, status: u16, response: &str) -> Self {
+        Self {
+            timestamp: OffsetDateTime::now_utc(),
+            request: request.to_string(),
+            payload: log::redact(payload),
+            status,
+            response: log::redact(response),
+        }
+    }
+}
+
+pub fn capture_to(dir: PathBuf) {
+    debug!("Capturing traffic to {:?}.", dir);
+    *CAPTURE_DIR.lock().unwrap() = Some(dir);
+}
+
+/// Keeps every exchange, to be served back with `BACA_REPLAY`.
+pub fn record_to(dir: PathBuf) {
+    debug!("Recording traffic to {:?}.", dir);
+    *RECORD_DIR.lock().unwrap() = Some(dir);
+}
+
+pub fn is_capturing() -> bool {
+    CAPTURE_DIR.lock().unwrap().is_some() || RECORD_DIR.lock().unwrap().is_some()
+}
+
+/// Saves the exchange, keeping only the last [`KEEP`] captured ones and all recorded ones.
+/// Failures are only logged.
+pub fn capture(exchange: &Exchange) {
+    if let Some(dir) = CAPTURE_DIR.lock().unwrap().clone() {
+        if let Err(e) = save(&dir, exchange).and_then(|_| prune(&dir)) {
+            error!("Error capturing traffic: {:?}", e);
+        }
+    }
+
+    if let Some(dir) = RECORD_DIR.lock().unwrap().clone() {
+        if let Err(e) = save(&dir, exchange) {
+            error!("Error recording traffic: {:?}", e);
+        }
+    }
+}
+
+fn save(dir: &Path, exchange: &Exchange) -> Result<()> {
+    fs::create_dir_all(dir)?;
+    let name = format!(
+        "{}-{}.yaml",
+        exchange.timestamp.unix_timestamp_nanos(),
+        exchange.request
+    );
+    fs::write(dir.join(name), serde_yaml::to_string(exchange)?)?;
+    Ok(())
+}
+
+fn prune(dir: &Path) -> Result<()> {
+    let captured = list(dir)?;
+    for old in &captured[..captured.len().saturating_sub(KEEP)] {
+        fs::remove_file(old)?;
+    }
+
+    Ok(())
+}
+
+/// Exchanges saved in `dir`, oldest first.
+pub fn read_all(dir: &Path) -> Result<Vec<Exchange>> {
+    list(dir)?
+        .iter()
+        .map(|x| Ok(serde_yaml::from_str(&fs::read_to_string(x)?)?))
+        .collect()
+}
+
+/// Captured exchanges, oldest first.
+pub fn list(dir: &Path) -> Result<Vec<PathBuf>> {
+    if !dir.is_dir() {
+        return Ok(Vec::new());
+    }
+
+    let mut captured = fs::read_dir(dir)?
+        .map(|x| x.map(|x| x.path()))
+        .collect::<std::io::Result<Vec<_>>>()?;
+    captured.retain(|x| x.extension().is_some_and(|x| x == "yaml"));
+    captured.sort();
+
+    Ok(captured)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert_fs::TempDir;
+
+    #[test]
+    fn only_last_exchanges_should_be_kept() {
+        let dir = TempDir::new().unwrap();
+
+        for i in 0..KEEP + 5 {
+            let mut exchange = Exchange::new("tasks", "payload", 200, "//OK");
+            exchange.timestamp =
+                OffsetDateTime::from_unix_timestamp(1_700_000_000 + i as i64).unwrap();
+            save(dir.path(), &exchange).unwrap();
+            prune(dir.path()).unwrap();
+        }
+
+        let captured = list(dir.path()).unwrap();
+        assert_eq!(captured.len(), KEEP);
+        assert!(captured[0].ends_with("1700000005000000000-tasks.yaml"));
+    }
+
+    #[test]
+    fn exchange_should_be_redacted() {
+        log::register_secret("traffic-test-password");
+
+        let exchange = Exchange::new(
+            "login",
+            "7|0|7|login|traffic-test-password|",
+            200,
+            "JSESSIONID=ABCDEF;",
+        );
+
+        assert_eq!(exchange.payload, "7|0|7|login|***|");
+        assert_eq!(exchange.response, "JSESSIONID=***;");
+    }
+}
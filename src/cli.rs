@@ -1,4 +1,5 @@
 use clap::{Parser, Subcommand};
+use std::path::PathBuf;
 
 // todo: AppSettings::ArgRequiredElseHel
 #[derive(Parser)]
@@ -16,6 +17,35 @@ pub(crate) struct Cli {
     #[arg(short = 'U', long)]
     pub force_update: bool,
 
+    /// Workspace directory, by default the nearest directory containing `.baca`
+    #[arg(long, global = true, value_name = "DIR")]
+    pub workspace: Option<PathBuf>,
+
+    /// Never prompt, use defaults or fail instead. Implied when stdin is not a terminal
+    #[arg(long, global = true)]
+    pub non_interactive: bool,
+
+    /// Never prompt like --non-interactive, but answer every confirmation with yes
+    #[arg(short = 'y', long, global = true)]
+    pub yes: bool,
+
+    // The connection options share their ids with the ones of `init`, so clap fills both.
+    /// BaCa hostname overriding the saved one, also set by BACA_HOST
+    #[arg(long, global = true, help_heading = "Connection")]
+    pub host: Option<String>,
+
+    /// BaCa login overriding the saved one, also set by BACA_LOGIN
+    #[arg(long, global = true, help_heading = "Connection")]
+    pub login: Option<String>,
+
+    /// BaCa password overriding the saved one. BACA_PASSWORD takes precedence, options show up in the process list
+    #[arg(long, global = true, help_heading = "Connection")]
+    pub password: Option<String>,
+
+    /// Session cookie overriding the saved one, also set by BACA_COOKIE
+    #[arg(long, global = true, help_heading = "Connection")]
+    pub cookie: Option<String>,
+
     #[command(subcommand)]
     pub command: Option<Commands>,
 }
@@ -35,6 +65,22 @@ pub(crate) enum Commands {
         /// BaCa password
         #[arg(long, short)]
         password: Option<String>,
+
+        /// Command printing the password, instead of storing it
+        #[arg(long, value_name = "CMD", conflicts_with = "password")]
+        password_command: Option<String>,
+
+        /// Ask for the password on every login
+        #[arg(long, conflicts_with_all = ["password", "password_command"])]
+        ask_password: bool,
+
+        /// Use credentials of the saved profile, creates it if it does not exist
+        #[arg(long)]
+        profile: Option<String>,
+
+        /// Encrypt the password and cookie of all workspaces with a passphrase
+        #[arg(long)]
+        encrypt: bool,
     },
 
     /// Get submit details
@@ -53,6 +99,16 @@ pub(crate) enum Commands {
         task: Option<u32>,
     },
 
+    /// Print the last N (default 10) submits recorded locally, along with copies of the sent files
+    Journal {
+        #[arg(default_value_t = 10)]
+        amount: u16,
+
+        /// Print only the specified task's submits, use 'baca tasks' to see what ids are available
+        #[arg(long, short, value_name = "TASK_ID")]
+        task: Option<u32>,
+    },
+
     /// Print available tasks
     Tasks {},
 
@@ -62,9 +118,21 @@ pub(crate) enum Commands {
         #[arg(long, short, value_name = "TASK_ID")]
         task: Option<u32>,
 
-        /// A file to submit, overrides saved path
-        #[arg(short, long, value_name = "FILE")]
-        file: Option<String>,
+        /// Files, directories or glob patterns to submit, overrides saved paths. Multiple files are zipped together
+        #[arg(short, long, value_name = "FILE", num_args = 1..)]
+        file: Vec<String>,
+
+        /// Submit only the selected files matching the pattern, e.g. '*.cpp'
+        #[arg(long, value_name = "PATTERN")]
+        include: Vec<String>,
+
+        /// Do not submit the selected files matching the pattern, e.g. '*_test.cpp'
+        #[arg(long, value_name = "PATTERN")]
+        exclude: Vec<String>,
+
+        /// Put all selected files in the archive root, without their directories
+        #[arg(long)]
+        flatten: bool,
 
         /// Task language. Please provide it exactly as is displayed on BaCa
         #[arg(short, long)]
@@ -86,7 +154,35 @@ pub(crate) enum Commands {
         #[arg(long)]
         no_save: bool,
 
-        /// Remove main function before submitting. Takes effect only on C/C++ files
+        /// Remove the byte order mark from the beginning of files
+        #[arg(long)]
+        strip_bom: bool,
+
+        /// Convert CRLF line endings to LF
+        #[arg(long)]
+        crlf_to_lf: bool,
+
+        /// Encoding of the files, e.g. 'windows-1250'. Files are converted to UTF-8 before submitting
+        #[arg(long)]
+        encoding: Option<String>,
+
+        /// Transliterate Unicode characters into ASCII only in 'comments' or only 'outside-strings'
+        #[arg(long, value_name = "SCOPE")]
+        transliterate: Option<String>,
+
+        /// Inline local headers included with quotes, for tasks accepting a single file. Takes effect only on C/C++ files
+        #[arg(long)]
+        inline_includes: bool,
+
+        /// Remove code between 'baca:strip-begin' and 'baca:strip-end' comments
+        #[arg(long)]
+        strip_debug: bool,
+
+        /// Remove '#ifdef DEBUG' and '#ifndef ONLINE_JUDGE' blocks as well, implies --strip-debug. Takes effect only on C/C++ files
+        #[arg(long)]
+        strip_debug_blocks: bool,
+
+        /// Remove main function before submitting. Takes effect only on C/C++ and Java files
         #[arg(long)]
         no_main: bool,
 
@@ -94,10 +190,46 @@ pub(crate) enum Commands {
         #[arg(long)]
         no_polish: bool,
 
+        /// Add a header rendered from the template in the submit config to files without one
+        #[arg(long)]
+        add_header: bool,
+
+        /// Require headers to contain your name or login
+        #[arg(long)]
+        strict_header: bool,
+
         /// Skip header verification
         #[arg(long)]
         skip_header: bool,
 
+        /// Compile the file locally before submitting, overrides saved config
+        #[arg(short, long)]
+        compile: bool,
+
+        /// Submit even if the local checks fail
+        #[arg(long)]
+        force: bool,
+
+        /// Submit even if the same file was already submitted to the task
+        #[arg(long)]
+        allow_duplicate: bool,
+
+        /// Prepare the submit, but do not send it. Prints what would be sent instead
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Print the content of the file that would be sent
+        #[arg(long, requires = "dry_run")]
+        show: bool,
+
+        /// Print a diff between the original and the file that would be sent
+        #[arg(long, requires = "dry_run")]
+        diff: bool,
+
+        /// Keep the transformed files that would be sent
+        #[arg(long, requires = "dry_run")]
+        keep: bool,
+
         #[command(subcommand)]
         command: Option<SubmitCommands>,
     },
@@ -109,11 +241,44 @@ pub(crate) enum Commands {
         task: Option<u32>,
     },
 
+    /// Stress test a solution against a brute-force reference on generated inputs
+    Stress {
+        /// Input generator, receives the test number as its first argument
+        #[arg(long = "gen", value_name = "FILE")]
+        generator: String,
+
+        /// Reference solution, assumed to be correct
+        #[arg(long, value_name = "FILE")]
+        brute: String,
+
+        /// Solution under test, defaults to the saved submit file
+        #[arg(short, long, value_name = "FILE")]
+        solution: Option<String>,
+
+        /// Number of tests to run
+        #[arg(short = 'n', long, default_value_t = 1000)]
+        iterations: u32,
+
+        /// Time limit for a single run in milliseconds
+        #[arg(long, value_name = "MS", default_value_t = 5000)]
+        timeout: u64,
+    },
+
     /// Open a editor to edit BaCa configuration
     Config {},
 
     /// Remove the whole `.baca` directory
     Clear {},
+
+    /// Zip debug info and the last N (default 10) requests captured with -vvv for a bug report
+    DebugReport {
+        #[arg(default_value_t = 10)]
+        amount: u16,
+
+        /// Output file, baca-debug-report.zip by default
+        #[arg(long, short, value_name = "FILE")]
+        output: Option<String>,
+    },
 }
 
 #[derive(Subcommand)]
@@ -124,3 +289,31 @@ pub(crate) enum SubmitCommands {
     /// Clear saved submit config
     Clear {},
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn connection_options_should_be_accepted_after_subcommand() {
+        let cli = Cli::try_parse_from(["baca", "tasks", "--host", "mn2020", "--login", "jaremko"])
+            .unwrap();
+
+        assert_eq!(cli.host.as_deref(), Some("mn2020"));
+        assert_eq!(cli.login.as_deref(), Some("jaremko"));
+    }
+
+    #[test]
+    fn connection_options_before_init_should_be_its_settings() {
+        let cli =
+            Cli::try_parse_from(["baca", "--host", "mn2020", "init", "-l", "jaremko"]).unwrap();
+
+        match cli.command {
+            Some(Commands::Init { host, login, .. }) => {
+                assert_eq!(host.as_deref(), Some("mn2020"));
+                assert_eq!(login.as_deref(), Some("jaremko"));
+            }
+            _ => panic!("init expected"),
+        }
+    }
+}
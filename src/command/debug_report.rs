@@ -0,0 +1,207 @@
+use crate::api::baca_api::BacaApi;
+use crate::api::traffic;
+use crate::command::Command;
+use crate::error::{Error, Result};
+use crate::log;
+use crate::update::CURRENT_VERSION;
+use crate::workspace::{ConfigObject, ConnectionConfig, SubmitConfig, UserConfig, Workspace};
+use colored::Colorize;
+use std::env::consts;
+use std::fs::File;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::{fs, io};
+use tracing::info;
+
+/// Bundles what is needed to reproduce an issue into a zip, with the secrets redacted.
+pub struct DebugReport {
+    pub output: PathBuf,
+    /// Number of captured exchanges to include.
+    pub last_n: usize,
+}
+
+impl DebugReport {
+    pub fn new(output: &Option<String>, last_n: u16) -> Self {
+        Self {
+            output: PathBuf::from(output.as_deref().unwrap_or("baca-debug-report.zip")),
+            last_n: last_n as usize,
+        }
+    }
+}
+
+impl Command for DebugReport {
+    fn execute<W, A>(self, workspace: &W, api: &A) -> Result<()>
+    where
+        W: Workspace,
+        A: BacaApi,
+    {
+        info!("Writing debug report to {:?}.", self.output);
+        let paths = workspace.get_paths();
+        let captured = traffic::list(&paths.traffic_dir())?;
+        let captured = &captured[captured.len().saturating_sub(self.last_n)..];
+
+        let mut report = String::new();
+        report += &format!("baca {}\n", CURRENT_VERSION);
+        report += &format!("OS: {} {}\n", consts::OS, consts::ARCH);
+        report += &format!("Workspace: {}\n", paths.root().display());
+        report += &format!("\n# Workspace layout\n{}", layout(&paths.baca_dir()));
+        report += &format!("\n# Configs\n{}", configs(workspace));
+        report += &format!("\n# Connectivity\n{}\n", check_connectivity(workspace, api));
+
+        if captured.is_empty() {
+            report += "\nNo captured traffic, run the failing command with -vvv first.\n";
+        }
+
+        write_zip(&self.output, &log::redact(&report), captured)
+            .map_err(|e| Error::Zipping(e.into()))?;
+
+        println!("Debug report saved to {}.", self.output.display());
+        println!(
+            "{}",
+            "Attach it to an issue at https://github.com/hjaremko/baca-cli/issues".bright_green()
+        );
+        Ok(())
+    }
+}
+
+/// Files in `.baca` with their sizes, without the contents.
+fn layout(baca_dir: &Path) -> String {
+    let mut files = Vec::new();
+    list_files(baca_dir, &mut files);
+    files.sort();
+
+    files
+        .iter()
+        .map(|x| {
+            let size = x.metadata().map(|x| x.len()).unwrap_or_default();
+            let name = x.strip_prefix(baca_dir).unwrap_or(x).display();
+            format!("{} ({} bytes)\n", name, size)
+        })
+        .collect()
+}
+
+fn list_files(dir: &Path, files: &mut Vec<PathBuf>) {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(_) => return,
+    };
+
+    for path in entries.flatten().map(|x| x.path()) {
+        match path.is_dir() {
+            true => list_files(&path, files),
+            false => files.push(path),
+        }
+    }
+}
+
+/// Configs as read from disk, with masked secrets and without decrypting them.
+fn configs<W: Workspace>(workspace: &W) -> String {
+    let connection = workspace.read_config_object::<ConnectionConfig>();
+    let submit = workspace.read_config_object::<SubmitConfig>();
+    let user = UserConfig::read_config(workspace);
+
+    format!(
+        "{:#?}\n{:#?}\n{:#?}\n",
+        connection.map_err(|e| e.to_string()),
+        submit.map_err(|e| e.to_string()),
+        user.map(|x| x.profiles).map_err(|e| e.to_string())
+    )
+}
+
+fn check_connectivity<W, A>(workspace: &W, api: &A) -> String
+where
+    W: Workspace,
+    A: BacaApi,
+{
+    let result = ConnectionConfig::read_config(workspace)
+        .and_then(|config| api.get_tasks(&config))
+        .map(|tasks| tasks.tasks.len());
+
+    match result {
+        Ok(count) => format!("OK, {} tasks available.", count),
+        Err(e) => format!("Failed: {}", e),
+    }
+}
+
+fn write_zip(output: &Path, report: &str, captured: &[PathBuf]) -> io::Result<()> {
+    let mut zip = zip::ZipWriter::new(File::create(output)?);
+    let options =
+        zip::write::FileOptions::default().compression_method(zip::CompressionMethod::DEFLATE);
+
+    zip.start_file("report.txt", options)?;
+    zip.write_all(report.as_bytes())?;
+
+    for exchange in captured {
+        let name = exchange.file_name().unwrap_or_default().to_string_lossy();
+        zip.start_file(format!("traffic/{}", name), options)?;
+        zip.write_all(log::redact(&fs::read_to_string(exchange)?).as_bytes())?;
+    }
+
+    zip.finish()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::baca_api::MockBacaApi;
+    use crate::api::traffic::Exchange;
+    use crate::model::Tasks;
+    use crate::workspace::workspace_dir::tests::{make_baca, make_temp_workspace};
+    use std::io::Read;
+
+    #[test]
+    fn report_should_contain_traffic_without_secrets() {
+        let (temp_dir, mock_paths, workspace) = make_temp_workspace().unwrap();
+        workspace.initialize().unwrap();
+        make_baca().save_config(&workspace).unwrap();
+        let traffic_dir = mock_paths.traffic_dir();
+        fs::create_dir_all(&traffic_dir).unwrap();
+        for (i, request) in ["login", "tasks", "results"].iter().enumerate() {
+            let exchange = Exchange::new(request, "payload", 200, "//OK[0,[],0,7]");
+            fs::write(
+                traffic_dir.join(format!("{}-{}.yaml", i, request)),
+                serde_yaml::to_string(&exchange).unwrap(),
+            )
+            .unwrap();
+        }
+
+        let mut mock_api = MockBacaApi::new();
+        mock_api
+            .expect_get_tasks()
+            .once()
+            .returning(|_| Ok(Tasks { tasks: Vec::new() }));
+
+        let output = temp_dir.path().join("report.zip");
+        let report = DebugReport {
+            output: output.clone(),
+            last_n: 2,
+        };
+        report.execute(&workspace, &mock_api).unwrap();
+
+        let mut archive = zip::ZipArchive::new(File::open(output).unwrap()).unwrap();
+        let mut names = archive.file_names().map(String::from).collect::<Vec<_>>();
+        names.sort();
+        assert_eq!(
+            names,
+            vec![
+                "report.txt",
+                "traffic/1-tasks.yaml",
+                "traffic/2-results.yaml"
+            ]
+        );
+
+        let mut text = String::new();
+        archive
+            .by_name("report.txt")
+            .unwrap()
+            .read_to_string(&mut text)
+            .unwrap();
+        assert!(text.contains("connection ("), "{}", text);
+        assert!(text.contains("OK, 0 tasks available."), "{}", text);
+        assert!(text.contains("test_login"), "{}", text);
+        assert!(!text.contains("test_pass"), "{}", text);
+        assert!(!text.contains("test_cookie"), "{}", text);
+        temp_dir.close().unwrap();
+    }
+}
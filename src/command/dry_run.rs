@@ -0,0 +1,155 @@
+use crate::error::Result;
+use crate::model::Task;
+use crate::workspace::SourceFile;
+use colored::Colorize;
+use similar::{ChangeTag, TextDiff};
+use std::fs;
+use std::path::Path;
+
+/// Summary of a prepared submit, printed instead of sending it.
+pub struct DryRunReport<'a> {
+    pub task: &'a Task,
+    /// Sources after all transforms, before zipping.
+    pub sources: &'a [&'a SourceFile],
+    /// The file that would be sent.
+    pub artifact: &'a Path,
+}
+
+impl DryRunReport<'_> {
+    pub fn print(&self, show_content: bool, show_diff: bool) -> Result<()> {
+        let size = fs::metadata(self.artifact)?.len();
+
+        println!("Task:     {} (id {})", self.task.problem_name, self.task.id);
+        println!("Language: {}", self.task.language.to_string());
+        println!("File:     {} ({} bytes)", file_name(self.artifact), size);
+
+        if self.is_archive() {
+            for source in self.sources {
+                println!("          {}", source.name.to_string_lossy());
+            }
+        }
+
+        for source in self.sources {
+            if show_content {
+                println!("{}", self.content_header(source).bold());
+                print!("{}", read_lossy(&source.path)?);
+            }
+
+            if show_diff {
+                print!("{}", make_diff(source)?);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn is_archive(&self) -> bool {
+        !matches!(self.sources, [source] if source.path == self.artifact)
+    }
+
+    fn content_header(&self, source: &SourceFile) -> String {
+        if self.is_archive() {
+            format!(
+                "Content of {} inside {}:",
+                source.name.to_string_lossy(),
+                file_name(self.artifact)
+            )
+        } else {
+            format!("Content of {}:", source.name.to_string_lossy())
+        }
+    }
+}
+
+fn make_diff(source: &SourceFile) -> Result<String> {
+    let original = read_lossy(&source.original)?;
+    let transformed = read_lossy(&source.path)?;
+
+    if original == transformed {
+        return Ok(format!(
+            "{}\n",
+            format!("No changes in {}.", source.name.to_string_lossy()).bold()
+        ));
+    }
+
+    let diff = TextDiff::from_lines(&original, &transformed);
+    let mut result = format!(
+        "{}\n{}\n",
+        format!("--- {}", source.original.to_string_lossy()).bold(),
+        format!("+++ {}", source.name.to_string_lossy()).bold()
+    );
+
+    for hunk in diff.unified_diff().context_radius(3).iter_hunks() {
+        result.push_str(&format!("{}\n", hunk.header().to_string().cyan()));
+
+        for change in hunk.iter_changes() {
+            let line = match change.tag() {
+                ChangeTag::Delete => format!("-{}", change).red(),
+                ChangeTag::Insert => format!("+{}", change).green(),
+                ChangeTag::Equal => format!(" {}", change).normal(),
+            };
+            result.push_str(&line.to_string());
+
+            if change.missing_newline() {
+                result.push('\n');
+            }
+        }
+    }
+
+    Ok(result)
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_name()
+        .map(|x| x.to_string_lossy().to_string())
+        .unwrap_or_default()
+}
+
+fn read_lossy(path: &Path) -> Result<String> {
+    Ok(String::from_utf8_lossy(&fs::read(path)?).to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert_fs::prelude::*;
+    use std::path::PathBuf;
+
+    #[test]
+    fn diff_should_contain_removed_lines() {
+        colored::control::set_override(false);
+        let dir = assert_fs::TempDir::new().unwrap();
+        let original = dir.child("original.cpp");
+        original
+            .write_str("// header\nint f() { return 1; }\nint main() {}\n")
+            .unwrap();
+        let transformed = dir.child("source.cpp");
+        transformed
+            .write_str("// header\nint f() { return 1; }\n")
+            .unwrap();
+        let mut source = SourceFile::new(original.to_path_buf(), PathBuf::from("source.cpp"));
+        source.path = transformed.to_path_buf();
+
+        let diff = make_diff(&source).unwrap();
+
+        assert!(diff.contains("-int main() {}\n"), "{}", diff);
+        assert!(diff.contains(" int f() { return 1; }\n"), "{}", diff);
+        assert!(
+            !diff
+                .lines()
+                .any(|x| x.starts_with('+') && !x.starts_with("+++")),
+            "{}",
+            diff
+        );
+    }
+
+    #[test]
+    fn identical_files_should_have_no_diff() {
+        colored::control::set_override(false);
+        let dir = assert_fs::TempDir::new().unwrap();
+        let original = dir.child("original.cpp");
+        original.write_str("// header\n").unwrap();
+        let source = SourceFile::new(original.to_path_buf(), PathBuf::from("original.cpp"));
+
+        assert_eq!(make_diff(&source).unwrap(), "No changes in original.cpp.\n");
+    }
+}
@@ -0,0 +1,254 @@
+use crate::api::baca_api::BacaApi;
+use crate::command::prompt;
+use crate::error::{Error, Result};
+use crate::model::{Results, Task};
+use crate::workspace::journal::PENDING_SUBMIT_ID;
+use crate::workspace::{ConfigObject, ConnectionConfig, Journal, Workspace};
+use colored::Colorize;
+use std::path::Path;
+use tracing::{debug, info};
+
+/// An earlier submit to the same task, which is probably the same file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Duplicate {
+    pub submit_id: String,
+    /// The file hash matches, not only its size.
+    pub identical: bool,
+}
+
+/// Looks for an earlier submit of the artifact, first in the journal by its hash,
+/// then among the submits made elsewhere by its size and language.
+pub fn find_duplicate(
+    journal: &Journal,
+    results: &Results,
+    task: &Task,
+    sha256: &str,
+    size: u64,
+) -> Option<Duplicate> {
+    let recorded = journal
+        .entries
+        .iter()
+        .filter(|x| x.task_id == task.id)
+        .collect::<Vec<_>>();
+
+    if let Some(entry) = recorded.iter().rev().find(|x| x.sha256 == sha256) {
+        return Some(Duplicate {
+            submit_id: entry.submit_id.clone(),
+            identical: true,
+        });
+    }
+
+    results
+        .submits
+        .iter()
+        .filter(|x| !recorded.iter().any(|entry| entry.submit_id == x.id))
+        .find(|x| u64::try_from(x.size).is_ok_and(|x| x == size) && x.has_language(task.language))
+        .map(|x| Duplicate {
+            submit_id: x.id.clone(),
+            identical: false,
+        })
+}
+
+/// Shows the verdict of an earlier submit of the same file and asks whether to send it again.
+/// A submit matching only by size may be an unrelated revision, so it is just shown as a warning.
+pub fn check_duplicate<W, A>(
+    workspace: &W,
+    api: &A,
+    connection_config: &ConnectionConfig,
+    task: &Task,
+    artifact: &Path,
+    sha256: &str,
+) -> Result<()>
+where
+    W: Workspace,
+    A: BacaApi,
+{
+    let journal = Journal::read_config(workspace)?;
+    let results = api.get_results_by_task(connection_config, &task.id)?;
+    let size = artifact.metadata()?.len();
+
+    let duplicate = match find_duplicate(&journal, &results, task, sha256, size) {
+        Some(duplicate) => duplicate,
+        None => return Ok(()),
+    };
+    info!("Duplicate: {:?}", duplicate);
+
+    if duplicate.submit_id == PENDING_SUBMIT_ID {
+        println!(
+            "{}",
+            "This file has already been sent to this task, but it did not show up in the results."
+                .bright_yellow()
+        );
+        return confirm_again(duplicate);
+    }
+
+    let earlier = api.get_submit_details(connection_config, &duplicate.submit_id)?;
+    debug!("Earlier submit: {:?}", earlier);
+
+    if !duplicate.identical {
+        println!(
+            "{}",
+            "A file of the same size has already been submitted to this task:".bright_yellow()
+        );
+        earlier.print();
+        return Ok(());
+    }
+
+    println!(
+        "{}",
+        "This file has already been submitted to this task:".bright_yellow()
+    );
+    earlier.print();
+    confirm_again(duplicate)
+}
+
+fn confirm_again(duplicate: Duplicate) -> Result<()> {
+    let proceed = prompt::confirm("Submit it again?", false)?;
+
+    match proceed {
+        true => Ok(()),
+        false => Err(Error::DuplicateSubmit(duplicate.submit_id)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::baca_api::MockBacaApi;
+    use crate::model::{Language, Submit};
+    use crate::workspace::journal::tests::make_entry;
+    use crate::workspace::MockWorkspace;
+    use assert_fs::prelude::*;
+
+    fn make_task() -> Task {
+        Task::new("1", Language::Cpp, "[A] Zera funkcji", 12)
+    }
+
+    fn make_submit(id: &str, size: i32, language: &str) -> Submit {
+        Submit {
+            id: id.to_string(),
+            size,
+            language: language.to_string(),
+            ..Default::default()
+        }
+    }
+
+    fn make_journal() -> Journal {
+        let mut first = make_entry("1", "100");
+        first.sha256 = "aaa".to_string();
+        let mut other_task = make_entry("2", "101");
+        other_task.sha256 = "bbb".to_string();
+        let mut second = make_entry("1", "102");
+        second.sha256 = "aaa".to_string();
+
+        Journal {
+            entries: vec![first, other_task, second],
+        }
+    }
+
+    #[test]
+    fn identical_file_should_match_latest_recorded_submit() {
+        let results = Results::new(vec![make_submit("102", 10, "C++")]);
+
+        let duplicate = find_duplicate(&make_journal(), &results, &make_task(), "aaa", 99);
+
+        assert_eq!(
+            duplicate,
+            Some(Duplicate {
+                submit_id: "102".to_string(),
+                identical: true,
+            })
+        );
+    }
+
+    #[test]
+    fn files_recorded_for_other_tasks_should_not_match() {
+        let duplicate = find_duplicate(
+            &make_journal(),
+            &Results::default(),
+            &make_task(),
+            "bbb",
+            10,
+        );
+
+        assert_eq!(duplicate, None);
+    }
+
+    #[test]
+    fn unrecorded_submits_should_match_by_size_and_language() {
+        let results = Results::new(vec![
+            make_submit("105", 10, "Java"),
+            make_submit("104", 11, "C++"),
+            make_submit("103", 10, "C++"),
+            make_submit("102", 10, "C++"),
+        ]);
+
+        let duplicate = find_duplicate(&make_journal(), &results, &make_task(), "ccc", 10);
+
+        assert_eq!(
+            duplicate,
+            Some(Duplicate {
+                submit_id: "103".to_string(),
+                identical: false,
+            })
+        );
+    }
+
+    #[test]
+    fn unrecorded_submits_should_match_language_reported_in_polish() {
+        let task = Task::new("1", Language::CppWithFileSupport, "[A] Zera funkcji", 12);
+        let results = Results::new(vec![
+            make_submit("104", 10, "C++"),
+            make_submit("103", 10, "C++ z obsluga plikow"),
+        ]);
+
+        let duplicate = find_duplicate(&make_journal(), &results, &task, "ccc", 10);
+
+        assert_eq!(
+            duplicate,
+            Some(Duplicate {
+                submit_id: "103".to_string(),
+                identical: false,
+            })
+        );
+    }
+
+    #[test]
+    fn recorded_submits_should_not_match_by_size() {
+        let results = Results::new(vec![make_submit("102", 10, "C++")]);
+
+        let duplicate = find_duplicate(&make_journal(), &results, &make_task(), "ccc", 10);
+
+        assert_eq!(duplicate, None);
+    }
+
+    #[test]
+    fn submit_matching_only_by_size_should_not_be_blocked() {
+        let dir = assert_fs::TempDir::new().unwrap();
+        let artifact = dir.child("source.cpp");
+        artifact.write_str("0123456789").unwrap();
+
+        let mut mock_workspace = MockWorkspace::new();
+        mock_workspace
+            .expect_read_config_object()
+            .returning(|| Ok(make_journal()));
+        let mut mock_api = MockBacaApi::new();
+        mock_api
+            .expect_get_results_by_task()
+            .returning(|_, _| Ok(Results::new(vec![make_submit("103", 10, "C++")])));
+        mock_api
+            .expect_get_submit_details()
+            .returning(|_, _| Ok(make_submit("103", 10, "C++")));
+
+        let result = check_duplicate(
+            &mock_workspace,
+            &mock_api,
+            &ConnectionConfig::default(),
+            &make_task(),
+            artifact.path(),
+            "ccc",
+        );
+
+        assert!(result.is_ok(), "{:?}", result);
+    }
+}
@@ -1,41 +1,78 @@
 use crate::api;
 use crate::api::baca_api::BacaApi;
-use crate::command::prompt::{Input, Password, Prompt};
+use crate::command::prompt::{
+    Input, NewPassphrase, Password, PasswordStorageChoice, ProfileChoice, Prompt,
+};
 use crate::command::Command;
 use crate::update::BacaRelease;
-use crate::workspace::{ConfigObject, Workspace};
+use crate::workspace::secret_store::remember;
+use crate::workspace::{ConfigObject, Encryption, Profile, UserConfig, Workspace};
 use crate::{error, workspace};
 use tracing::{debug, info};
 
+/// How the password is kept in the workspace.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PasswordStorage {
+    Plain,
+    Command,
+    Prompt,
+}
+
 pub struct Init {
     host: Option<String>,
     login: Option<String>,
     password: Option<String>,
+    password_command: Option<String>,
+    ask_password: bool,
+    profile: Option<String>,
+    encrypt: bool,
     login_prompt: Box<dyn Prompt>,
     password_prompt: Box<dyn Prompt>,
+    password_command_prompt: Box<dyn Prompt>,
     host_prompt: Box<dyn Prompt>,
+    passphrase_prompt: Box<dyn Prompt>,
+    /// Chooses from the saved profiles when not set.
+    profile_prompt: Option<Box<dyn Prompt>>,
+    /// Chooses how to keep the password when not set.
+    storage_prompt: Option<Box<dyn Prompt>>,
 }
 
 impl Init {
-    pub fn new(host: Option<String>, login: Option<String>, password: Option<String>) -> Self {
+    pub fn new(
+        host: Option<String>,
+        login: Option<String>,
+        password: Option<String>,
+        password_command: Option<String>,
+        ask_password: bool,
+        profile: Option<String>,
+        encrypt: bool,
+    ) -> Self {
         Self {
             host,
             login,
             password,
-            login_prompt: Box::new(Input("Login")),
+            password_command,
+            ask_password,
+            profile,
+            encrypt,
+            login_prompt: Box::new(Input("Login", "--login")),
             password_prompt: Box::new(Password {}),
-            host_prompt: Box::new(Input("Host")),
+            password_command_prompt: Box::new(Input("Password command", "--password-command")),
+            host_prompt: Box::new(Input("Host", "--host")),
+            passphrase_prompt: Box::new(NewPassphrase),
+            profile_prompt: None,
+            storage_prompt: None,
         }
     }
 }
 
 impl Init {
-    fn get_host(&self) -> error::Result<String> {
-        if self.host.as_ref().is_none() {
-            return self.host_prompt.interact();
+    fn get_host(&self, profile: Option<&Profile>) -> error::Result<String> {
+        if let Some(host) = self.host.as_ref().or(profile.and_then(|x| x.host.as_ref())) {
+            return Ok(host.clone());
         }
 
-        Ok(self.host.as_ref().unwrap().clone())
+        self.host_prompt.interact()
     }
 
     fn get_login(&self) -> error::Result<String> {
@@ -53,6 +90,63 @@ impl Init {
 
         Ok(self.password.as_ref().unwrap().clone())
     }
+
+    fn get_password_command(&self) -> error::Result<String> {
+        if self.password_command.as_ref().is_none() {
+            return self.password_command_prompt.interact();
+        }
+
+        Ok(self.password_command.as_ref().unwrap().clone())
+    }
+
+    fn get_password_storage(&self) -> error::Result<PasswordStorage> {
+        if self.password.is_some() {
+            return Ok(PasswordStorage::Plain);
+        }
+
+        if self.password_command.is_some() {
+            return Ok(PasswordStorage::Command);
+        }
+
+        if self.ask_password {
+            return Ok(PasswordStorage::Prompt);
+        }
+
+        let choice = match &self.storage_prompt {
+            Some(prompt) => prompt.interact()?,
+            None => PasswordStorageChoice.interact()?,
+        };
+
+        Ok(match choice.as_str() {
+            "command" => PasswordStorage::Command,
+            "prompt" => PasswordStorage::Prompt,
+            _ => PasswordStorage::Plain,
+        })
+    }
+
+    fn has_credentials(&self) -> bool {
+        self.login.is_some()
+            || self.password.is_some()
+            || self.password_command.is_some()
+            || self.ask_password
+    }
+
+    /// Profile given explicitly, or chosen from the existing ones when no credentials are given.
+    fn get_profile_name(&self, user_config: &UserConfig) -> error::Result<Option<String>> {
+        if self.profile.is_some() {
+            return Ok(self.profile.clone());
+        }
+
+        if self.has_credentials() || user_config.profiles.is_empty() {
+            return Ok(None);
+        }
+
+        let choice = match &self.profile_prompt {
+            Some(prompt) => prompt.interact()?,
+            None => ProfileChoice::new(user_config.profile_names()).interact()?,
+        };
+        Ok(Some(choice).filter(|x| !x.is_empty()))
+    }
 }
 
 impl Command for Init {
@@ -64,20 +158,43 @@ impl Command for Init {
     {
         info!("Initializing Baca workspace.");
 
-        let host = self.get_host()?;
-        let login = self.get_login()?;
-        let password = self.get_password()?;
+        let mut user_config = UserConfig::read_config(workspace)?;
+        let profile_name = self.get_profile_name(&user_config)?;
+        let profile = profile_name
+            .as_ref()
+            .and_then(|x| user_config.profiles.get(x))
+            .cloned();
+        debug!("Profile: {:?}", profile_name);
+
+        let host = self.get_host(profile.as_ref())?;
+        let (login, storage) = match &profile {
+            Some(profile) => (profile.login.clone(), None),
+            None => (self.get_login()?, Some(self.get_password_storage()?)),
+        };
+        let (password, password_command) = match (&profile, storage) {
+            (Some(profile), _) => (
+                user_config.profile_password(workspace, profile)?,
+                profile.password_command.clone(),
+            ),
+            (None, Some(PasswordStorage::Command)) => {
+                (String::new(), Some(self.get_password_command()?))
+            }
+            (None, _) => (self.get_password()?, None),
+        };
 
         debug!("Host: {}", host);
         debug!("Login: {}", login);
-        debug!("Password: {}", password);
+        debug!("Password storage: {:?}", storage);
 
         let mut config = workspace::ConnectionConfig {
             host,
             login,
             password,
+            password_command,
             permutation: api::details::permutation(),
             cookie: "".to_string(),
+            secrets: None,
+            profile: profile_name.clone(),
         };
 
         let cleanup_directory = |e| match e {
@@ -92,6 +209,49 @@ impl Command for Init {
 
         workspace.initialize().map_err(cleanup_directory)?;
         config.cookie = api.get_cookie(&config).map_err(cleanup_directory)?;
+
+        // Needed only to log in now, it will be asked for again on the next login.
+        if storage == Some(PasswordStorage::Prompt) {
+            config.password.clear();
+        }
+
+        let mut user_config_changed = false;
+
+        if self.encrypt && user_config.encryption.is_none() {
+            println!("Setting up the passphrase encrypting credentials of all workspaces.");
+            let passphrase = self.passphrase_prompt.interact()?;
+            let (encryption, key) = Encryption::new(&passphrase)?;
+            remember(&workspace.get_paths(), &encryption, &key).map_err(cleanup_directory)?;
+            user_config.encryption = Some(encryption);
+            user_config_changed = true;
+        }
+
+        if user_config.encryption.is_some() {
+            config.secrets = Some(String::new());
+        }
+
+        if let (Some(name), None) = (profile_name, profile) {
+            println!("Saving new profile {}.", name);
+            user_config.profiles.insert(
+                name.clone(),
+                Profile {
+                    login: config.login.clone(),
+                    password: config.password.clone(),
+                    password_command: config.password_command.clone(),
+                    host: Some(config.host.clone()),
+                    ..Default::default()
+                },
+            );
+            user_config.default_profile.get_or_insert(name);
+            user_config_changed = true;
+        }
+
+        if user_config_changed {
+            user_config
+                .save_config(workspace)
+                .map_err(cleanup_directory)?;
+        }
+
         config.save_config(workspace).map_err(cleanup_directory)?;
         save_version(workspace)
     }
@@ -108,7 +268,9 @@ mod tests {
     use crate::api;
     use crate::api::baca_api::MockBacaApi;
     use crate::command::prompt::MockPrompt;
-    use crate::workspace::{ConnectionConfig, MockWorkspace};
+    use crate::workspace::{ConnectionConfig, MockWorkspace, WorkspacePaths};
+    use std::fs;
+    use std::sync::{Arc, Mutex};
 
     // todo: tests::utils
     fn make_mock_connection_config() -> ConnectionConfig {
@@ -116,8 +278,11 @@ mod tests {
             host: "host".to_string(),
             login: "login".to_string(),
             password: "pass".to_string(),
+            password_command: None,
             permutation: api::details::permutation(),
             cookie: "".to_string(),
+            secrets: None,
+            profile: None,
         }
     }
 
@@ -147,14 +312,20 @@ mod tests {
             host,
             login,
             password,
+            password_command: None,
             permutation: api::details::permutation(),
             cookie: "ok_cookie".to_string(),
+            secrets: None,
+            profile: None,
         }
     }
 
     #[test]
     fn success_test() {
         let mut mock_workspace = MockWorkspace::new();
+        mock_workspace
+            .expect_read_config_object::<UserConfig>()
+            .returning(|| Ok(UserConfig::default()));
         mock_workspace
             .expect_initialize()
             .once()
@@ -183,9 +354,17 @@ mod tests {
             host: Some("host".to_string()),
             login: Some("login".to_string()),
             password: Some("pass".to_string()),
-            login_prompt: Box::new(Input("Login")),
+            password_command: None,
+            ask_password: false,
+            profile: None,
+            encrypt: false,
+            login_prompt: Box::new(Input("Login", "--login")),
             password_prompt: Box::new(Password {}),
-            host_prompt: Box::new(Input("Host")),
+            password_command_prompt: Box::new(make_never_called_prompt_mock()),
+            host_prompt: Box::new(Input("Host", "--host")),
+            passphrase_prompt: Box::new(make_never_called_prompt_mock()),
+            profile_prompt: None,
+            storage_prompt: Some(Box::new(make_never_called_prompt_mock())),
         };
         let result = init.execute(&mock_workspace, &mock_api);
         assert!(result.is_ok())
@@ -198,6 +377,9 @@ mod tests {
         let host_prompt_mock = make_never_called_prompt_mock();
 
         let mut mock_workspace = MockWorkspace::new();
+        mock_workspace
+            .expect_read_config_object::<UserConfig>()
+            .returning(|| Ok(UserConfig::default()));
         mock_workspace
             .expect_initialize()
             .once()
@@ -223,9 +405,17 @@ mod tests {
             host: Some("host".to_string()),
             login: None,
             password: Some("pass".to_string()),
+            password_command: None,
+            ask_password: false,
+            profile: None,
+            encrypt: false,
             login_prompt: Box::new(login_prompt_mock),
             password_prompt: Box::new(password_prompt_mock),
+            password_command_prompt: Box::new(make_never_called_prompt_mock()),
             host_prompt: Box::new(host_prompt_mock),
+            passphrase_prompt: Box::new(make_never_called_prompt_mock()),
+            profile_prompt: None,
+            storage_prompt: Some(Box::new(make_never_called_prompt_mock())),
         };
 
         let result = init.execute(&mock_workspace, &mock_api);
@@ -239,6 +429,9 @@ mod tests {
         let host_prompt_mock = make_never_called_prompt_mock();
 
         let mut mock_workspace = MockWorkspace::new();
+        mock_workspace
+            .expect_read_config_object::<UserConfig>()
+            .returning(|| Ok(UserConfig::default()));
         mock_workspace
             .expect_initialize()
             .once()
@@ -263,9 +456,17 @@ mod tests {
             host: Some("host".to_string()),
             login: Some("login".to_string()),
             password: None,
+            password_command: None,
+            ask_password: false,
+            profile: None,
+            encrypt: false,
             login_prompt: Box::new(login_prompt_mock),
             password_prompt: Box::new(password_prompt_mock),
+            password_command_prompt: Box::new(make_never_called_prompt_mock()),
             host_prompt: Box::new(host_prompt_mock),
+            passphrase_prompt: Box::new(make_never_called_prompt_mock()),
+            profile_prompt: None,
+            storage_prompt: Some(Box::new(make_prompt_mock("plain"))),
         };
 
         let result = init.execute(&mock_workspace, &mock_api);
@@ -287,6 +488,10 @@ mod tests {
             .expect_save_config_object::<BacaRelease>()
             .returning(|_| Ok(()));
 
+        mock_workspace
+            .expect_read_config_object::<UserConfig>()
+            .returning(|| Ok(UserConfig::default()));
+
         let expected_config = make_baca_config("host", "prompt_login", "prompt_password");
         let expected_cookie = expected_config.cookie.clone();
         mock_workspace
@@ -303,9 +508,17 @@ mod tests {
             host: Some("host".to_string()),
             login: None,
             password: None,
+            password_command: None,
+            ask_password: false,
+            profile: None,
+            encrypt: false,
             login_prompt: Box::new(login_prompt_mock),
             password_prompt: Box::new(password_prompt_mock),
+            password_command_prompt: Box::new(make_never_called_prompt_mock()),
             host_prompt: Box::new(host_prompt_mock),
+            passphrase_prompt: Box::new(make_never_called_prompt_mock()),
+            profile_prompt: None,
+            storage_prompt: Some(Box::new(make_prompt_mock("plain"))),
         };
 
         let result = init.execute(&mock_workspace, &mock_api);
@@ -319,6 +532,9 @@ mod tests {
         let host_prompt_mock = make_prompt_mock("prompt_host");
 
         let mut mock_workspace = MockWorkspace::new();
+        mock_workspace
+            .expect_read_config_object::<UserConfig>()
+            .returning(|| Ok(UserConfig::default()));
         mock_workspace
             .expect_initialize()
             .once()
@@ -343,12 +559,339 @@ mod tests {
             host: None,
             login: Some("login".to_string()),
             password: Some("pass".to_string()),
+            password_command: None,
+            ask_password: false,
+            profile: None,
+            encrypt: false,
             login_prompt: Box::new(input_prompt_mock),
             password_prompt: Box::new(password_prompt_mock),
+            password_command_prompt: Box::new(make_never_called_prompt_mock()),
             host_prompt: Box::new(host_prompt_mock),
+            passphrase_prompt: Box::new(make_never_called_prompt_mock()),
+            profile_prompt: None,
+            storage_prompt: Some(Box::new(make_never_called_prompt_mock())),
         };
 
         let result = init.execute(&mock_workspace, &mock_api);
         assert!(result.is_ok())
     }
+
+    /// Paths with the user config directory and the profiles file in it.
+    fn make_user_dir_paths() -> (assert_fs::TempDir, WorkspacePaths) {
+        let dir = assert_fs::TempDir::new().unwrap();
+        let paths = WorkspacePaths::with_root(dir.path()).with_user_dir(Some(dir.to_path_buf()));
+        fs::write(paths.config_path::<UserConfig>(), "").unwrap();
+        (dir, paths)
+    }
+
+    fn make_user_config() -> UserConfig {
+        let mut user_config = UserConfig::default();
+        user_config.profiles.insert(
+            "uj".to_string(),
+            Profile {
+                login: "profile_login".to_string(),
+                password: "profile_pass".to_string(),
+                host: Some("profile_host".to_string()),
+                ..Default::default()
+            },
+        );
+        user_config
+    }
+
+    #[test]
+    fn chosen_profile_should_provide_credentials() {
+        let mut mock_workspace = MockWorkspace::new();
+        mock_workspace
+            .expect_initialize()
+            .once()
+            .returning(|| Ok(()));
+        mock_workspace
+            .expect_save_config_object::<BacaRelease>()
+            .returning(|_| Ok(()));
+        mock_workspace
+            .expect_read_config_object::<UserConfig>()
+            .returning(|| Ok(make_user_config()));
+        mock_workspace
+            .expect_save_config_object::<UserConfig>()
+            .never();
+        mock_workspace
+            .expect_save_config_object()
+            .once()
+            .withf(|x: &ConnectionConfig| {
+                x.host == "profile_host"
+                    && x.login.is_empty()
+                    && x.password.is_empty()
+                    && x.profile.as_deref() == Some("uj")
+            })
+            .returning(|_| Ok(()));
+
+        let mut mock_api = MockBacaApi::new();
+        mock_api
+            .expect_get_cookie()
+            .withf(|x| x.login == "profile_login" && x.password == "profile_pass")
+            .returning(|_| Ok("ok_cookie".to_string()));
+
+        let init = Init {
+            host: None,
+            login: None,
+            password: None,
+            password_command: None,
+            ask_password: false,
+            profile: None,
+            encrypt: false,
+            login_prompt: Box::new(make_never_called_prompt_mock()),
+            password_prompt: Box::new(make_never_called_prompt_mock()),
+            password_command_prompt: Box::new(make_never_called_prompt_mock()),
+            host_prompt: Box::new(make_never_called_prompt_mock()),
+            passphrase_prompt: Box::new(make_never_called_prompt_mock()),
+            profile_prompt: Some(Box::new(make_prompt_mock("uj"))),
+            storage_prompt: Some(Box::new(make_never_called_prompt_mock())),
+        };
+
+        let result = init.execute(&mock_workspace, &mock_api);
+        assert!(result.is_ok(), "{:?}", result);
+    }
+
+    #[test]
+    fn new_profile_should_be_saved() {
+        let (_user_dir, paths) = make_user_dir_paths();
+        let mut mock_workspace = MockWorkspace::new();
+        mock_workspace
+            .expect_initialize()
+            .once()
+            .returning(|| Ok(()));
+        mock_workspace
+            .expect_get_paths()
+            .returning(move || paths.clone());
+        mock_workspace
+            .expect_save_config_object::<BacaRelease>()
+            .returning(|_| Ok(()));
+        let saved_user_config = Arc::new(Mutex::new(UserConfig::default()));
+        let saved_clone = saved_user_config.clone();
+        mock_workspace
+            .expect_read_config_object::<UserConfig>()
+            .returning(move || Ok(saved_clone.lock().unwrap().clone()));
+        mock_workspace
+            .expect_save_config_object::<UserConfig>()
+            .once()
+            .withf(|x: &UserConfig| {
+                x.default_profile.as_deref() == Some("uj")
+                    && x.profile("uj").unwrap()
+                        == &Profile {
+                            login: "login".to_string(),
+                            password: "pass".to_string(),
+                            host: Some("host".to_string()),
+                            ..Default::default()
+                        }
+            })
+            .returning(move |x| {
+                *saved_user_config.lock().unwrap() = x.clone();
+                Ok(())
+            });
+        mock_workspace
+            .expect_save_config_object()
+            .once()
+            .withf(|x: &ConnectionConfig| {
+                x.profile.as_deref() == Some("uj") && x.login.is_empty() && x.password.is_empty()
+            })
+            .returning(|_| Ok(()));
+
+        let mut mock_api = MockBacaApi::new();
+        mock_api
+            .expect_get_cookie()
+            .returning(|_| Ok("ok_cookie".to_string()));
+
+        let init = Init {
+            host: Some("host".to_string()),
+            login: None,
+            password: None,
+            password_command: None,
+            ask_password: false,
+            profile: Some("uj".to_string()),
+            encrypt: false,
+            login_prompt: Box::new(make_prompt_mock("login")),
+            password_prompt: Box::new(make_prompt_mock("pass")),
+            password_command_prompt: Box::new(make_never_called_prompt_mock()),
+            host_prompt: Box::new(make_never_called_prompt_mock()),
+            passphrase_prompt: Box::new(make_never_called_prompt_mock()),
+            profile_prompt: Some(Box::new(make_never_called_prompt_mock())),
+            storage_prompt: Some(Box::new(make_prompt_mock("plain"))),
+        };
+
+        let result = init.execute(&mock_workspace, &mock_api);
+        assert!(result.is_ok(), "{:?}", result);
+    }
+
+    #[test]
+    fn new_profile_password_should_be_encrypted() {
+        let (encryption, key) = Encryption::with_rounds("passphrase", 10).unwrap();
+        let (_user_dir, paths) = make_user_dir_paths();
+        remember(&paths, &encryption, &key).unwrap();
+        let profile_key = key.clone();
+        fs::create_dir_all(paths.baca_dir()).unwrap();
+        fs::write(paths.config_path::<ConnectionConfig>(), "").unwrap();
+
+        let mut mock_workspace = MockWorkspace::new();
+        mock_workspace
+            .expect_initialize()
+            .once()
+            .returning(|| Ok(()));
+        mock_workspace
+            .expect_get_paths()
+            .returning(move || paths.clone());
+        mock_workspace
+            .expect_save_config_object::<BacaRelease>()
+            .returning(|_| Ok(()));
+        let saved_user_config = Arc::new(Mutex::new(UserConfig {
+            encryption: Some(encryption),
+            ..Default::default()
+        }));
+        let saved_clone = saved_user_config.clone();
+        mock_workspace
+            .expect_read_config_object::<UserConfig>()
+            .returning(move || Ok(saved_clone.lock().unwrap().clone()));
+        mock_workspace
+            .expect_save_config_object::<UserConfig>()
+            .once()
+            .withf(move |x: &UserConfig| {
+                let profile = x.profile("uj").unwrap();
+                let secret = profile
+                    .secret
+                    .as_deref()
+                    .and_then(|x| profile_key.decrypt(x).ok());
+                profile.password.is_empty() && secret.as_deref() == Some(b"pass".as_slice())
+            })
+            .returning(move |x| {
+                *saved_user_config.lock().unwrap() = x.clone();
+                Ok(())
+            });
+        mock_workspace
+            .expect_save_config_object()
+            .once()
+            .withf(move |x: &ConnectionConfig| {
+                let secrets = x
+                    .secrets
+                    .as_deref()
+                    .and_then(|x| key.decrypt(x).ok())
+                    .map(|x| String::from_utf8(x).unwrap())
+                    .unwrap_or_default();
+                x.password.is_empty() && secrets.contains(r#""password":"""#)
+            })
+            .returning(|_| Ok(()));
+
+        let mut mock_api = MockBacaApi::new();
+        mock_api
+            .expect_get_cookie()
+            .returning(|_| Ok("ok_cookie".to_string()));
+
+        let init = Init {
+            host: Some("host".to_string()),
+            login: None,
+            password: None,
+            password_command: None,
+            ask_password: false,
+            profile: Some("uj".to_string()),
+            encrypt: false,
+            login_prompt: Box::new(make_prompt_mock("login")),
+            password_prompt: Box::new(make_prompt_mock("pass")),
+            password_command_prompt: Box::new(make_never_called_prompt_mock()),
+            host_prompt: Box::new(make_never_called_prompt_mock()),
+            passphrase_prompt: Box::new(make_never_called_prompt_mock()),
+            profile_prompt: Some(Box::new(make_never_called_prompt_mock())),
+            storage_prompt: Some(Box::new(make_prompt_mock("plain"))),
+        };
+
+        let result = init.execute(&mock_workspace, &mock_api);
+        assert!(result.is_ok(), "{:?}", result);
+    }
+
+    fn make_storage_test_workspace(
+        check: impl Fn(&ConnectionConfig) -> bool + Send + 'static,
+    ) -> MockWorkspace {
+        let mut mock_workspace = MockWorkspace::new();
+        mock_workspace
+            .expect_read_config_object::<UserConfig>()
+            .returning(|| Ok(UserConfig::default()));
+        mock_workspace
+            .expect_initialize()
+            .once()
+            .returning(|| Ok(()));
+        mock_workspace
+            .expect_save_config_object::<BacaRelease>()
+            .returning(|_| Ok(()));
+        mock_workspace
+            .expect_save_config_object()
+            .once()
+            .withf(check)
+            .returning(|_| Ok(()));
+        mock_workspace
+    }
+
+    #[test]
+    fn password_command_should_be_saved_instead_of_password() {
+        let mock_workspace = make_storage_test_workspace(|x: &ConnectionConfig| {
+            x.password.is_empty() && x.password_command.as_deref() == Some("echo pass")
+        });
+
+        let mut mock_api = MockBacaApi::new();
+        mock_api
+            .expect_get_cookie()
+            .once()
+            .withf(|x| x.credentials().unwrap() == ("login".to_string(), "pass".to_string()))
+            .returning(|_| Ok("ok_cookie".to_string()));
+
+        let init = Init {
+            host: Some("host".to_string()),
+            login: Some("login".to_string()),
+            password: None,
+            password_command: None,
+            ask_password: false,
+            profile: None,
+            encrypt: false,
+            login_prompt: Box::new(make_never_called_prompt_mock()),
+            password_prompt: Box::new(make_never_called_prompt_mock()),
+            password_command_prompt: Box::new(make_prompt_mock("echo pass")),
+            host_prompt: Box::new(make_never_called_prompt_mock()),
+            passphrase_prompt: Box::new(make_never_called_prompt_mock()),
+            profile_prompt: None,
+            storage_prompt: Some(Box::new(make_prompt_mock("command"))),
+        };
+
+        let result = init.execute(&mock_workspace, &mock_api);
+        assert!(result.is_ok(), "{:?}", result);
+    }
+
+    #[test]
+    fn asked_password_should_not_be_saved() {
+        let mock_workspace = make_storage_test_workspace(|x: &ConnectionConfig| {
+            x.password.is_empty() && x.password_command.is_none()
+        });
+
+        let mut mock_api = MockBacaApi::new();
+        mock_api
+            .expect_get_cookie()
+            .once()
+            .withf(|x| x.password == "prompt_password")
+            .returning(|_| Ok("ok_cookie".to_string()));
+
+        let init = Init {
+            host: Some("host".to_string()),
+            login: Some("login".to_string()),
+            password: None,
+            password_command: None,
+            ask_password: true,
+            profile: None,
+            encrypt: false,
+            login_prompt: Box::new(make_never_called_prompt_mock()),
+            password_prompt: Box::new(make_prompt_mock("prompt_password")),
+            password_command_prompt: Box::new(make_never_called_prompt_mock()),
+            host_prompt: Box::new(make_never_called_prompt_mock()),
+            passphrase_prompt: Box::new(make_never_called_prompt_mock()),
+            profile_prompt: None,
+            storage_prompt: Some(Box::new(make_never_called_prompt_mock())),
+        };
+
+        let result = init.execute(&mock_workspace, &mock_api);
+        assert!(result.is_ok(), "{:?}", result);
+    }
 }
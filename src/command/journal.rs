@@ -0,0 +1,167 @@
+use crate::api::baca_api::BacaApi;
+use crate::command::Command;
+use crate::error::{Error, Result};
+use crate::workspace::{ConfigObject, Journal, JournalEntry, Workspace};
+use colored::Colorize;
+use std::path::Path;
+use time::macros::format_description;
+use tracing::info;
+
+/// Prints submits recorded locally, newest first.
+pub struct ShowJournal {
+    pub last_n: usize,
+    pub task_id: Option<String>,
+}
+
+impl ShowJournal {
+    pub fn new(last_n: u16, task_id: &Option<u32>) -> Self {
+        Self {
+            last_n: last_n as usize,
+            task_id: task_id.map(|x| x.to_string()),
+        }
+    }
+
+    fn select<'a>(&self, journal: &'a Journal) -> Vec<&'a JournalEntry> {
+        journal
+            .entries
+            .iter()
+            .rev()
+            .filter(|x| self.task_id.as_ref().is_none_or(|id| x.task_id == *id))
+            .take(self.last_n)
+            .collect()
+    }
+}
+
+impl Command for ShowJournal {
+    fn execute<W, A>(self, workspace: &W, _api: &A) -> Result<()>
+    where
+        W: Workspace,
+        A: BacaApi,
+    {
+        let journal = Journal::read_config(workspace)?;
+        let entries = self.select(&journal);
+        info!("Printing {} journal entries.", entries.len());
+
+        if entries.is_empty() {
+            return Err(Error::NoSubmitsYet);
+        }
+
+        for entry in entries {
+            println!("\n{}", format_entry(entry));
+        }
+
+        Ok(())
+    }
+}
+
+fn format_entry(entry: &JournalEntry) -> String {
+    let timestamp = entry
+        .timestamp
+        .format(format_description!(
+            "[year]-[month]-[day] [hour]:[minute]:[second] UTC"
+        ))
+        .unwrap_or_default();
+    let files = entry
+        .files
+        .iter()
+        .map(|x| file_name(x))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let transforms = match entry.transforms.is_empty() {
+        true => "no transforms".to_string(),
+        false => entry.transforms.join(", "),
+    };
+
+    let details = match entry.is_pending() {
+        true => "submit id unknown, check 'baca log'".to_string(),
+        false => format!("baca details {}", entry.submit_id.bright_yellow()),
+    };
+
+    format!(
+        "{} (task {}) - {} - {}\n├─── {}\n├─── {}\n├─── sha256 {}\n├─── {}\n└─── {}",
+        entry.task_name.bright_green(),
+        entry.task_id,
+        entry.language.to_string(),
+        timestamp,
+        files,
+        transforms,
+        &entry.sha256[..entry.sha256.len().min(12)],
+        entry.snapshot.to_string_lossy(),
+        details
+    )
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_name()
+        .unwrap_or(path.as_os_str())
+        .to_string_lossy()
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::baca_api::MockBacaApi;
+    use crate::workspace::journal::tests::make_entry;
+    use crate::workspace::MockWorkspace;
+
+    fn make_journal() -> Journal {
+        Journal {
+            entries: vec![
+                make_entry("1", "100"),
+                make_entry("2", "101"),
+                make_entry("1", "102"),
+                make_entry("1", "103"),
+            ],
+        }
+    }
+
+    fn ids(entries: &[&JournalEntry]) -> Vec<String> {
+        entries.iter().map(|x| x.submit_id.clone()).collect()
+    }
+
+    #[test]
+    fn newest_entries_should_go_first() {
+        let journal = make_journal();
+
+        let entries = ShowJournal::new(3, &None).select(&journal);
+
+        assert_eq!(ids(&entries), vec!["103", "102", "101"]);
+    }
+
+    #[test]
+    fn entries_should_be_filtered_by_task() {
+        let journal = make_journal();
+
+        let entries = ShowJournal::new(10, &Some(2)).select(&journal);
+
+        assert_eq!(ids(&entries), vec!["101"]);
+    }
+
+    #[test]
+    fn entry_should_link_to_details() {
+        colored::control::set_override(false);
+        let mut entry = make_entry("1", "4242");
+        entry.sha256 = "ba7816bf8f01cfea414140de5dae2223".to_string();
+
+        let formatted = format_entry(&entry);
+
+        assert_eq!(
+            formatted,
+            "[A] Zera funkcji (task 1) - C++ - 2023-11-14 22:13:20 UTC\n├─── main.cpp\n├─── no-main\n├─── sha256 ba7816bf8f01\n├─── \n└─── baca details 4242"
+        );
+    }
+
+    #[test]
+    fn empty_journal_should_return_error() {
+        let mut mock_workspace = MockWorkspace::new();
+        mock_workspace
+            .expect_read_config_object::<Journal>()
+            .returning(|| Ok(Journal::default()));
+        let mock_api = MockBacaApi::new();
+
+        let result = ShowJournal::new(3, &None).execute(&mock_workspace, &mock_api);
+
+        assert!(matches!(result, Err(Error::NoSubmitsYet)), "{:?}", result);
+    }
+}
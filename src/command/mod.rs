@@ -1,22 +1,32 @@
 use crate::api::baca_api::BacaApi;
 use crate::cli::Commands;
+use crate::command::debug_report::DebugReport;
 use crate::command::details::Details;
 use crate::command::init::Init;
+use crate::command::journal::ShowJournal;
 use crate::command::last::Last;
 use crate::command::log::Log;
 use crate::command::refresh::Refresh;
-use crate::command::submit::{SaveSwitch, Submit, SubmitSubcommand};
+use crate::command::stress::Stress;
+use crate::command::submit::{SaveSwitch, Submit, SubmitOptions, SubmitSubcommand};
 use crate::command::tasks::Tasks;
 use crate::error;
 use crate::workspace::config_editor::ConfigEditor;
-use crate::workspace::{ConnectionConfig, SubmitConfig, Workspace};
+use crate::workspace::{ConnectionConfig, HeaderConfig, NormalizeConfig, SubmitConfig, Workspace};
+use std::path::PathBuf;
+use std::time::Duration;
 
+mod debug_report;
 mod details;
+mod dry_run;
+mod duplicate;
 mod init;
+mod journal;
 mod last;
 mod log;
 mod prompt;
 mod refresh;
+mod stress;
 mod submit;
 mod tasks;
 
@@ -34,7 +44,20 @@ where
             host,
             login,
             password,
-        } => Init::new(host.clone(), login.clone(), password.clone()).execute(workspace, api),
+            password_command,
+            ask_password,
+            profile,
+            encrypt,
+        } => Init::new(
+            host.clone(),
+            login.clone(),
+            password.clone(),
+            password_command.clone(),
+            *ask_password,
+            profile.clone(),
+            *encrypt,
+        )
+        .execute(workspace, api),
         Commands::Details { submit_id } => {
             Details::new(&submit_id.to_string()).execute(workspace, api)
         }
@@ -43,24 +66,49 @@ where
             let log = Log::new(&amount.to_string(), task);
             log.execute(workspace, api)
         }
+        Commands::Journal { amount, task } => {
+            ShowJournal::new(*amount, task).execute(workspace, api)
+        }
         Commands::Tasks {} => Tasks::new().execute(workspace, api),
         Commands::Submit {
             task,
             file,
+            include,
+            exclude,
+            flatten,
             language,
             rename,
             save,
             zip,
             no_save,
+            strip_bom,
+            crlf_to_lf,
+            encoding,
+            transliterate,
+            inline_includes,
+            strip_debug,
+            strip_debug_blocks,
             no_main,
             no_polish,
+            add_header,
+            strict_header,
             skip_header,
+            compile,
+            force,
+            allow_duplicate,
+            dry_run,
+            show,
+            diff,
+            keep,
             command,
         } => {
             let subcommand = SubmitSubcommand::from(command);
             let save_switch = SaveSwitch::new(*save, *no_save);
             let mut provided_config = SubmitConfig {
-                file: None,
+                files: Vec::new(),
+                include: include.clone(),
+                exclude: exclude.clone(),
+                flatten: *flatten,
                 language: match language {
                     None => None,
                     Some(lang_str) => Some(lang_str.parse()?),
@@ -68,16 +116,43 @@ where
                 id: task.map(|x| x.to_string()),
                 rename_as: rename.clone(),
                 to_zip: *zip,
+                normalize: NormalizeConfig {
+                    strip_bom: *strip_bom,
+                    crlf_to_lf: *crlf_to_lf,
+                    encoding: encoding.clone(),
+                    transliterate: match transliterate {
+                        None => None,
+                        Some(scope) => Some(scope.parse()?),
+                    },
+                },
+                inline_includes: *inline_includes,
+                strip_debug: *strip_debug,
+                strip_debug_blocks: *strip_debug_blocks,
                 no_main: *no_main,
                 no_polish: *no_polish,
                 skip_header: *skip_header,
+                header: HeaderConfig {
+                    add: *add_header,
+                    strict: *strict_header,
+                    ..Default::default()
+                },
+                compile: *compile,
+                transforms: Vec::new(),
             };
-            provided_config.try_set_file(file.as_ref())?;
+            provided_config.try_set_files(file)?;
 
             Submit {
                 subcommand,
                 save_switch,
                 provided_config,
+                options: SubmitOptions {
+                    force: *force,
+                    allow_duplicate: *allow_duplicate,
+                    dry_run: *dry_run,
+                    show_content: *show,
+                    show_diff: *diff,
+                    keep: *keep,
+                },
             }
             .execute(workspace, api)
         }
@@ -89,10 +164,27 @@ where
             };
             task.execute(workspace, api)
         }
+        Commands::Stress {
+            generator,
+            brute,
+            solution,
+            iterations,
+            timeout,
+        } => Stress {
+            generator: PathBuf::from(generator),
+            brute: PathBuf::from(brute),
+            solution: solution.as_ref().map(PathBuf::from),
+            iterations: *iterations,
+            timeout: Duration::from_millis(*timeout),
+        }
+        .execute(workspace, api),
         Commands::Config {} => {
             ConfigEditor::new().edit::<W, ConnectionConfig>(workspace)?;
             Ok(())
         }
         Commands::Clear {} => workspace.remove_workspace(),
+        Commands::DebugReport { amount, output } => {
+            DebugReport::new(output, *amount).execute(workspace, api)
+        }
     }
 }
@@ -1,20 +1,33 @@
 use crate::error;
+use crate::interactive::{self, Mode};
 use crate::model::Tasks;
 use dialoguer::theme::ColorfulTheme;
-use dialoguer::Select;
+use dialoguer::{Confirm, Select};
 #[cfg(test)]
 use mockall::{automock, predicate::*};
+use std::env;
 use tracing::info;
 
 #[cfg_attr(test, automock)]
 pub trait Prompt {
-    fn interact(&self) -> error::Result<String>;
+    fn interact(&self) -> error::Result<String> {
+        self.interact_in(interactive::mode())
+    }
+
+    /// Answers in the given mode, instead of the one set for the whole run.
+    fn interact_in(&self, mode: Mode) -> error::Result<String>;
 }
 
-pub struct Input(pub &'static str);
+/// Asks for a value that can also be given as an argument, e.g. `Input("Login", "--login")`.
+pub struct Input(pub &'static str, pub &'static str);
 
 impl Prompt for Input {
-    fn interact(&self) -> error::Result<String> {
+    fn interact_in(&self, mode: Mode) -> error::Result<String> {
+        interactive::require_in(
+            mode,
+            &format!("the {} with {}", self.0.to_lowercase(), self.1),
+        )?;
+
         Ok(dialoguer::Input::<String>::new()
             .with_prompt(self.0)
             .interact()?)
@@ -24,13 +37,33 @@ impl Prompt for Input {
 pub struct Password;
 
 impl Prompt for Password {
-    fn interact(&self) -> error::Result<String> {
+    fn interact_in(&self, mode: Mode) -> error::Result<String> {
+        interactive::require_in(mode, "the password with --password")?;
+
         Ok(dialoguer::Password::new()
             .with_prompt("Password")
             .interact()?)
     }
 }
 
+/// Asks for a new passphrase twice, in non-interactive mode takes it from `BACA_PASSPHRASE`.
+pub struct NewPassphrase;
+
+impl Prompt for NewPassphrase {
+    fn interact_in(&self, mode: Mode) -> error::Result<String> {
+        if mode != Mode::Interactive {
+            return env::var("BACA_PASSPHRASE").map_err(|_| {
+                error::Error::NotInteractive("the passphrase with BACA_PASSPHRASE".to_string())
+            });
+        }
+
+        Ok(dialoguer::Password::new()
+            .with_prompt("New passphrase")
+            .with_confirmation("Repeat passphrase", "Passphrases do not match.")
+            .interact()?)
+    }
+}
+
 pub struct TaskChoice {
     available_tasks: Tasks,
 }
@@ -42,7 +75,8 @@ impl TaskChoice {
 }
 
 impl Prompt for TaskChoice {
-    fn interact(&self) -> error::Result<String> {
+    fn interact_in(&self, mode: Mode) -> error::Result<String> {
+        interactive::require_in(mode, "a task id with --task")?;
         let items = &self.available_tasks.tasks;
 
         let selection = Select::with_theme(&ColorfulTheme::default())
@@ -55,3 +89,108 @@ impl Prompt for TaskChoice {
         Ok(items[selection].id.clone())
     }
 }
+
+/// Chooses one of the saved profiles, returns an empty string for new credentials.
+pub struct ProfileChoice {
+    profiles: Vec<String>,
+}
+
+impl ProfileChoice {
+    pub fn new(profiles: Vec<String>) -> Self {
+        Self { profiles }
+    }
+}
+
+impl Prompt for ProfileChoice {
+    fn interact_in(&self, mode: Mode) -> error::Result<String> {
+        interactive::require_in(mode, "a profile with --profile")?;
+        let mut items = self.profiles.clone();
+        items.push("Enter new credentials".to_string());
+
+        let selection = Select::with_theme(&ColorfulTheme::default())
+            .items(&items)
+            .with_prompt("Choose profile:")
+            .default(0)
+            .interact()?;
+
+        info!("Selection index: {}", selection);
+        Ok(self.profiles.get(selection).cloned().unwrap_or_default())
+    }
+}
+
+/// Chooses how to keep the password: `plain`, `command` or `prompt`.
+pub struct PasswordStorageChoice;
+
+impl Prompt for PasswordStorageChoice {
+    fn interact_in(&self, mode: Mode) -> error::Result<String> {
+        if mode != Mode::Interactive {
+            return Ok("plain".to_string());
+        }
+
+        let items = [
+            ("plain", "Store it in plain text"),
+            ("command", "Get it from a command, e.g. 'pass show uj/baca'"),
+            ("prompt", "Ask for it on every login"),
+        ];
+
+        let selection = Select::with_theme(&ColorfulTheme::default())
+            .items(&items.map(|x| x.1))
+            .with_prompt("How to keep the password?")
+            .default(0)
+            .interact()?;
+
+        info!("Selection index: {}", selection);
+        Ok(items[selection].0.to_string())
+    }
+}
+
+/// Asks a yes or no question, in non-interactive mode answers with `default`, with `--yes` with yes.
+pub fn confirm(prompt: &str, default: bool) -> error::Result<bool> {
+    confirm_in(interactive::mode(), prompt, default)
+}
+
+fn confirm_in(mode: Mode, prompt: &str, default: bool) -> error::Result<bool> {
+    let answer = match mode {
+        Mode::Interactive => {
+            return Ok(Confirm::new()
+                .with_prompt(prompt)
+                .default(default)
+                .interact()?)
+        }
+        Mode::Defaults => default,
+        Mode::AssumeYes => true,
+    };
+
+    info!("{} {}", prompt, answer);
+    Ok(answer)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::Error;
+
+    #[test]
+    fn prompts_should_fail_or_use_defaults_when_not_interactive() {
+        let login = Input("Login", "--login").interact_in(Mode::Defaults);
+        let task = TaskChoice::new(Tasks { tasks: Vec::new() }).interact_in(Mode::Defaults);
+        let storage = PasswordStorageChoice.interact_in(Mode::Defaults);
+        let save = confirm_in(Mode::Defaults, "Save submit configuration?", true);
+        let again = confirm_in(Mode::Defaults, "Submit it again?", false);
+
+        assert!(matches!(login, Err(Error::NotInteractive(x)) if x == "the login with --login"));
+        assert!(matches!(task, Err(Error::NotInteractive(x)) if x == "a task id with --task"));
+        assert_eq!(storage.unwrap(), "plain");
+        assert!(save.unwrap());
+        assert!(!again.unwrap());
+    }
+
+    #[test]
+    fn confirmations_should_be_answered_with_yes_when_assumed() {
+        let login = Input("Login", "--login").interact_in(Mode::AssumeYes);
+        let again = confirm_in(Mode::AssumeYes, "Submit it again?", false);
+
+        assert!(matches!(login, Err(Error::NotInteractive(_))));
+        assert!(again.unwrap());
+    }
+}
@@ -0,0 +1,284 @@
+use crate::api::baca_api::BacaApi;
+use crate::command::Command;
+use crate::error::{Error, Result};
+use crate::model::Language;
+use crate::toolchain::{compile, CompilerConfig, Program, RunOutput, RunStatus};
+use crate::workspace::{ConfigObject, SubmitConfig, Workspace};
+use colored::Colorize;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tracing::{debug, info};
+
+pub struct Stress {
+    pub generator: PathBuf,
+    pub brute: PathBuf,
+    pub solution: Option<PathBuf>,
+    pub iterations: u32,
+    pub timeout: Duration,
+}
+
+enum Failure {
+    Mismatch,
+    Crash(Option<i32>),
+    Timeout,
+}
+
+impl Command for Stress {
+    fn execute<W, A>(self, workspace: &W, _api: &A) -> Result<()>
+    where
+        W: Workspace,
+        A: BacaApi,
+    {
+        workspace.check_if_initialized()?;
+
+        let submit_config = SubmitConfig::read_config(workspace);
+        if let Err(Error::WorkspaceCorrupted) = submit_config {
+            return Err(Error::WorkspaceCorrupted);
+        }
+        let submit_config = submit_config.unwrap_or_default();
+        let (solution, solution_language) = self.resolve_solution(&submit_config)?;
+        info!(
+            "Solution under test: {:?} ({:?})",
+            solution, solution_language
+        );
+
+        let compilers = CompilerConfig::read_config(workspace)?;
+        let build_dir = tempfile::tempdir()?;
+        let generator = compile_by_extension(&compilers, &self.generator, build_dir.path())?;
+        let brute = compile_by_extension(&compilers, &self.brute, build_dir.path())?;
+        let solution = compile_source(&compilers, &solution_language, &solution, build_dir.path())?;
+
+        let input_path = build_dir.path().join("input.txt");
+
+        for i in 1..=self.iterations {
+            print!("\rTest {}/{}", i, self.iterations);
+            std::io::stdout().flush()?;
+
+            let input = self.run_helper(&generator, &self.generator, &[i.to_string()], None)?;
+            fs::write(&input_path, &input)?;
+            let expected = self.run_helper(&brute, &self.brute, &[], Some(&input_path))?;
+            let actual = solution.run(&[], Some(&input_path), self.timeout)?;
+
+            if let Some(failure) = check(&actual, &expected) {
+                println!();
+                let saved = save_test_case(workspace, &submit_config, &input, &expected)?;
+                report(&failure, &input, &expected, &actual);
+                return Err(Error::StressTestFailed(saved.to_string_lossy().to_string()));
+            }
+        }
+
+        println!();
+        println!(
+            "{}",
+            format!("All {} tests passed.", self.iterations).green()
+        );
+        Ok(())
+    }
+}
+
+impl Stress {
+    fn resolve_solution(&self, submit_config: &SubmitConfig) -> Result<(PathBuf, Language)> {
+        let language_of = |path: &Path| {
+            Language::from_path(path)
+                .ok_or_else(|| Error::UnsupportedLanguage(path.to_string_lossy().to_string()))
+        };
+
+        if let Some(solution) = &self.solution {
+            if !solution.exists() {
+                return Err(Error::InputFileDoesNotExist);
+            }
+
+            return Ok((solution.clone(), language_of(solution)?));
+        }
+
+        let solution = submit_config
+            .file()
+            .ok_or_else(|| Error::SubmitArgumentNotProvided("solution file".to_string()))?;
+        let language = match submit_config.language {
+            Some(language) => language,
+            None => language_of(solution)?,
+        };
+
+        Ok((solution.to_path_buf(), language))
+    }
+
+    fn run_helper(
+        &self,
+        program: &Program,
+        source: &Path,
+        args: &[String],
+        input: Option<&Path>,
+    ) -> Result<String> {
+        let output = program.run(args, input, self.timeout)?;
+        debug!("{:?} output: {:?}", source, output);
+
+        if output.status != RunStatus::Exited {
+            println!();
+            print!("{}", output.stderr);
+            return Err(Error::StressProgramFailed(
+                source.to_string_lossy().to_string(),
+            ));
+        }
+
+        Ok(output.stdout)
+    }
+}
+
+fn compile_by_extension(
+    compilers: &CompilerConfig,
+    source: &Path,
+    build_dir: &Path,
+) -> Result<Program> {
+    let language = Language::from_path(source)
+        .ok_or_else(|| Error::UnsupportedLanguage(source.to_string_lossy().to_string()))?;
+    compile_source(compilers, &language, source, build_dir)
+}
+
+fn compile_source(
+    compilers: &CompilerConfig,
+    language: &Language,
+    source: &Path,
+    build_dir: &Path,
+) -> Result<Program> {
+    if !source.exists() {
+        return Err(Error::InputFileDoesNotExist);
+    }
+
+    let compiler = compilers.compiler_for(language)?;
+    println!("Compiling {}.", source.to_string_lossy());
+
+    // Every program gets its own directory, so sources sharing a name do not overwrite each other.
+    let build_dir = tempfile::tempdir_in(build_dir)?.into_path();
+    compile(language, &compiler, source, &build_dir)
+}
+
+fn check(actual: &RunOutput, expected: &str) -> Option<Failure> {
+    match actual.status {
+        RunStatus::TimedOut => Some(Failure::Timeout),
+        RunStatus::Crashed(code) => Some(Failure::Crash(code)),
+        RunStatus::Exited if !outputs_match(&actual.stdout, expected) => Some(Failure::Mismatch),
+        RunStatus::Exited => None,
+    }
+}
+
+fn outputs_match(actual: &str, expected: &str) -> bool {
+    actual.split_whitespace().eq(expected.split_whitespace())
+}
+
+fn save_test_case<W: Workspace>(
+    workspace: &W,
+    submit_config: &SubmitConfig,
+    input: &str,
+    expected: &str,
+) -> Result<PathBuf> {
+    let task_dir = submit_config
+        .id()
+        .map(|id| id.to_string())
+        .unwrap_or_else(|| "local".to_string());
+    let dir = workspace.get_paths().tests_dir().join(task_dir);
+    fs::create_dir_all(&dir)?;
+
+    let mut n = 1;
+    let input_path = loop {
+        let path = dir.join(format!("stress_{}.in", n));
+        if !path.exists() {
+            break path;
+        }
+        n += 1;
+    };
+
+    fs::write(&input_path, input)?;
+    fs::write(input_path.with_extension("out"), expected)?;
+    info!("Saved failing test case as {:?}", input_path);
+
+    Ok(input_path)
+}
+
+fn report(failure: &Failure, input: &str, expected: &str, actual: &RunOutput) {
+    let verdict = match failure {
+        Failure::Mismatch => "Wrong answer".to_string(),
+        Failure::Crash(Some(code)) => format!("Runtime error (exit code {})", code),
+        Failure::Crash(None) => "Runtime error (killed by signal)".to_string(),
+        Failure::Timeout => "Time limit exceeded".to_string(),
+    };
+
+    println!("{}", verdict.bright_red());
+    println!("{}\n{}", "Input:".bold(), input);
+    println!("{}\n{}", "Expected:".bold(), expected);
+    println!("{}\n{}", "Actual:".bold(), actual.stdout);
+
+    if !actual.stderr.is_empty() {
+        println!("{}\n{}", "Stderr:".bold(), actual.stderr);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::baca_api::MockBacaApi;
+    use crate::workspace::workspace_dir::tests::make_temp_workspace;
+    use assert_fs::prelude::*;
+
+    fn make_stress(dir: &assert_fs::TempDir, solution: &str) -> Stress {
+        let generator = dir.child("gen.sh");
+        generator.write_str("echo $(( $1 % 7 )) 3\n").unwrap();
+        let brute = dir.child("brute.sh");
+        brute.write_str("read a b\necho $(( a + b ))\n").unwrap();
+        let solution_file = dir.child("solution.sh");
+        solution_file.write_str(solution).unwrap();
+
+        Stress {
+            generator: generator.to_path_buf(),
+            brute: brute.to_path_buf(),
+            solution: Some(solution_file.to_path_buf()),
+            iterations: 10,
+            timeout: Duration::from_secs(5),
+        }
+    }
+
+    #[test]
+    fn outputs_should_be_compared_by_tokens() {
+        assert!(outputs_match("1 2\n3\n", "1  2 3"));
+        assert!(!outputs_match("1 2", "1 2 3"));
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn correct_solution_should_pass() {
+        let (dir, _, workspace) = make_temp_workspace().unwrap();
+        workspace.initialize().unwrap();
+        let stress = make_stress(&dir, "read a b\necho $(( b + a ))\n");
+
+        let result = stress.execute(&workspace, &MockBacaApi::new());
+
+        assert!(result.is_ok(), "{:?}", result);
+        assert!(!workspace.get_paths().tests_dir().exists());
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn wrong_solution_should_save_failing_input() {
+        let (dir, _, workspace) = make_temp_workspace().unwrap();
+        workspace.initialize().unwrap();
+        let stress = make_stress(
+            &dir,
+            "read a b\nif [ $a -eq 4 ]; then echo 0; else echo $(( a + b )); fi\n",
+        );
+
+        let result = stress.execute(&workspace, &MockBacaApi::new());
+
+        assert!(
+            matches!(result, Err(Error::StressTestFailed(_))),
+            "{:?}",
+            result
+        );
+        let saved = workspace.get_paths().tests_dir().join("local/stress_1.in");
+        assert_eq!(fs::read_to_string(&saved).unwrap(), "4 3\n");
+        assert_eq!(
+            fs::read_to_string(saved.with_extension("out")).unwrap(),
+            "7\n"
+        );
+    }
+}
@@ -1,18 +1,27 @@
 use crate::api::baca_api::BacaApi;
 use crate::cli::SubmitCommands;
-use crate::command::last::Last;
+use crate::command::dry_run::DryRunReport;
+use crate::command::duplicate::check_duplicate;
 use crate::command::prompt::Prompt;
 use crate::command::{prompt, Command};
 use crate::error::{Error, Result};
-use crate::model::Language;
+use crate::model::{Language, Task};
+use crate::toolchain::CompilerConfig;
+use crate::transform::Pipeline;
 use crate::workspace::config_editor::ConfigEditor;
-use crate::workspace::header_check::is_header_present;
-use crate::workspace::{ConfigObject, ConnectionConfig, SubmitConfig, Workspace};
-use crate::{error, workspace};
+use crate::workspace::header_check::read_header;
+use crate::workspace::journal;
+use crate::workspace::lint::lint_file;
+use crate::workspace::{
+    ConfigObject, ConnectionConfig, HeaderConfig, HeaderFields, Journal, JournalEntry, LintConfig,
+    SourceFile, StagingDir, SubmitConfig, Workspace,
+};
+use crate::{error, model, toolchain, transform, workspace};
 use colored::Colorize;
-use dialoguer::Confirm;
 use merge::Merge;
 use std::fs;
+use std::path::{Path, PathBuf};
+use time::OffsetDateTime;
 use tracing::{debug, info};
 
 #[derive(Default)]
@@ -55,10 +64,22 @@ impl SaveSwitch {
     }
 }
 
+/// One-off switches, which are never saved with the submit config.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct SubmitOptions {
+    pub force: bool,
+    pub allow_duplicate: bool,
+    pub dry_run: bool,
+    pub show_content: bool,
+    pub show_diff: bool,
+    pub keep: bool,
+}
+
 pub struct Submit {
     pub subcommand: SubmitSubcommand,
     pub save_switch: SaveSwitch,
     pub provided_config: SubmitConfig,
+    pub options: SubmitOptions,
 }
 
 impl Command for Submit {
@@ -73,7 +94,7 @@ impl Command for Submit {
             SubmitSubcommand::None => {
                 let (ask_for_save, submit_config) = self.prepare_submit_config(workspace, api)?;
                 self.handle_config_save(workspace, ask_for_save, &submit_config)?;
-                submit(workspace, api, submit_config)
+                submit(workspace, api, submit_config, &self.options)
             }
         }
     }
@@ -111,7 +132,7 @@ impl Submit {
     {
         let (ask_for_save, mut submit_config) = self.merge_saved_and_provided_configs(workspace)?;
 
-        if submit_config.file().is_none() {
+        if submit_config.files().is_empty() {
             return Err(Error::SubmitArgumentNotProvided("file".to_string()));
         }
 
@@ -174,10 +195,7 @@ impl Submit {
     }
 
     fn prompt_for_save<W: Workspace>(workspace: &W, submit_config: &SubmitConfig) -> Result<()> {
-        let proceed = Confirm::new()
-            .with_prompt("Save submit configuration?")
-            .default(true)
-            .interact()?;
+        let proceed = prompt::confirm("Save submit configuration?", true)?;
 
         if proceed {
             submit_config.save_config(workspace)?;
@@ -193,6 +211,11 @@ impl Submit {
         ask_for_save: bool,
         submit_config: &SubmitConfig,
     ) -> Result<()> {
+        if self.options.dry_run {
+            info!("Dry run, submit config not saved");
+            return Ok(());
+        }
+
         match self.save_switch {
             SaveSwitch::None => {
                 info!("Ask for save? {}", ask_for_save);
@@ -213,7 +236,41 @@ impl Submit {
     }
 }
 
-fn submit<W, A>(workspace: &W, api: &A, mut submit_config: SubmitConfig) -> error::Result<()>
+fn submit<W, A>(
+    workspace: &W,
+    api: &A,
+    submit_config: SubmitConfig,
+    options: &SubmitOptions,
+) -> error::Result<()>
+where
+    W: Workspace,
+    A: BacaApi,
+{
+    let staging = StagingDir::new()?;
+    let result = submit_staged(workspace, api, submit_config, options, &staging);
+
+    match result {
+        Err(e) if !staging.is_empty() => {
+            let path = staging.keep();
+            println!("Staged files kept in {}", path.to_string_lossy());
+            Err(e)
+        }
+        Ok(()) if options.dry_run && options.keep => {
+            let path = staging.keep();
+            println!("Staged files kept in {}", path.to_string_lossy());
+            Ok(())
+        }
+        result => result,
+    }
+}
+
+fn submit_staged<W, A>(
+    workspace: &W,
+    api: &A,
+    submit_config: SubmitConfig,
+    options: &SubmitOptions,
+    staging: &StagingDir,
+) -> error::Result<()>
 where
     W: Workspace,
     A: BacaApi,
@@ -224,99 +281,319 @@ where
     let mut task = tasks.get_by_id(&task_id)?.clone();
     task.language = submit_config.language.unwrap();
 
-    let original_filename = submit_config
-        .file()
-        .unwrap()
-        .file_name()
-        .unwrap()
-        .to_str()
-        .unwrap()
-        .to_string();
-
-    let rename = if let Some(new_name) = &submit_config.rename_as {
-        if new_name == &original_filename {
-            original_filename
-        } else {
-            let renamed = std::env::temp_dir().join(new_name);
-            fs::copy(submit_config.file().unwrap(), &renamed)?;
-            submit_config.try_set_file(renamed.into())?;
-
-            format!(
-                "{} as {}",
-                &original_filename,
-                &submit_config
-                    .file()
-                    .unwrap()
-                    .file_name()
-                    .unwrap()
-                    .to_str()
-                    .unwrap()
-            )
-        }
-    } else {
-        original_filename
+    if options.dry_run {
+        println!(
+            "{}",
+            "Dry run, nothing will be sent to BaCa.".bright_yellow()
+        );
+    }
+
+    let mut sources = workspace::select_sources(&submit_config)?;
+    let header_fields = HeaderFields {
+        name: submit_config
+            .header
+            .name
+            .clone()
+            .unwrap_or_else(|| connection_config.login.clone()),
+        login: connection_config.login.clone(),
+        task: task.problem_name.clone(),
     };
+    let pipeline = transform::make_pipeline(&submit_config, sources.len(), &header_fields)?;
 
     println!(
         "Submitting {} to task {} ({}).",
-        rename.bright_yellow(),
+        describe_sources(&sources, &submit_config.rename_as).bright_yellow(),
         task.problem_name.bright_green(),
         task.language.to_string()
     );
 
-    if submit_config.no_main {
-        info!("Main removal enabled");
-        println!("Submitting with no main included");
+    transform::run_pipeline(&pipeline, &mut sources, staging)?;
+    let artifact = sources[0].path.clone();
+    let files = sources[0].files();
 
-        let unmained_input = workspace::remove_main(submit_config.file().unwrap())?;
-        submit_config.try_set_file(unmained_input.into())?;
-        info!("Unmained input file: {:?}", submit_config.file());
+    if submit_config.compile {
+        compile_check(
+            workspace,
+            &files,
+            &submit_config.language.unwrap(),
+            options,
+            &staging.step("build")?,
+        )?;
+    }
+
+    if !submit_config.skip_header {
+        check_headers(
+            &files,
+            &submit_config.language.unwrap(),
+            &submit_config.header,
+            &connection_config.login,
+        )?;
     }
 
-    if submit_config.no_polish {
-        info!("Polish diacritics removal enabled");
-        println!("Submitting with no Polish diacritics");
+    lint_check(
+        workspace,
+        &task_id,
+        &files,
+        &submit_config.language.unwrap(),
+        options,
+    )?;
 
-        let polishless_input = workspace::make_polishless_file(submit_config.file().unwrap())?;
-        submit_config.try_set_file(polishless_input.into())?;
-        info!("Polishless input file: {:?}", submit_config.file());
+    if options.dry_run {
+        let report = DryRunReport {
+            task: &task,
+            sources: &files,
+            artifact: &artifact,
+        };
+        return report.print(options.show_content, options.show_diff);
     }
 
-    if submit_config.to_zip {
-        submit_config.try_set_file(
-            workspace::zip_file(submit_config.file().unwrap())?
-                .to_path_buf()
-                .into(),
+    let sha256 = journal::hash_file(&artifact)?;
+    if !options.allow_duplicate {
+        check_duplicate(
+            workspace,
+            api,
+            &connection_config,
+            &task,
+            &artifact,
+            &sha256,
         )?;
+    }
+
+    let sent_at = OffsetDateTime::now_utc();
+    let submitted = api.submit(&connection_config, &task, artifact.to_str().unwrap());
+    let submit_id = match &submitted {
+        Ok(id) => id.as_str(),
+        Err(Error::SubmitNotFound) => journal::PENDING_SUBMIT_ID,
+        Err(_) => return submitted.map(|_| ()),
+    };
+
+    let entry = make_journal_entry(&task, submit_id, sent_at, &files, &pipeline, sha256);
+    if let Err(e) = Journal::record(workspace, entry, &artifact) {
         println!(
-            "Zipped as {}",
-            submit_config
-                .file()
-                .unwrap()
-                .file_name()
-                .unwrap()
-                .to_str()
-                .unwrap()
+            "{}",
+            format!("Submit was not recorded in the journal: {}", e).bright_yellow()
         );
-    };
+    }
+    let submit_id = submitted?;
 
-    if !submit_config.to_zip
-        && !submit_config.skip_header
-        && !is_header_present(
-            submit_config.file().unwrap(),
-            &submit_config.language.unwrap(),
-        )?
-    {
-        return Err(Error::NoHeader);
+    let details = api.get_submit_details(&connection_config, &submit_id)?;
+    details.print_with_tests();
+    verify_upload(&details, &artifact)
+}
+
+/// Makes sure BaCa received the file that was meant to be sent.
+///
+/// Submit details carry no source code, so only the sizes can be compared.
+fn verify_upload(details: &model::Submit, artifact: &Path) -> Result<()> {
+    let expected = artifact.metadata()?.len();
+    debug!(
+        "Sent {} bytes, BaCa received {} bytes",
+        expected, details.size
+    );
+
+    if u64::try_from(details.size).is_ok_and(|x| x == expected) {
+        return Ok(());
     }
 
-    api.submit(
-        &connection_config,
-        &task,
-        submit_config.file().unwrap().to_str().unwrap(),
-    )?;
+    println!(
+        "\n{}",
+        "!!! BaCa received a different file than the one prepared for the submit !!!"
+            .bright_red()
+            .bold()
+    );
+    println!(
+        "{}",
+        format!(
+            "Sent file: {} ({} bytes), received: {} bytes.",
+            artifact.to_string_lossy(),
+            expected,
+            details.size
+        )
+        .bright_red()
+    );
+
+    Err(Error::UploadMismatch(
+        details.id.clone(),
+        expected,
+        details.size,
+    ))
+}
+
+fn make_journal_entry(
+    task: &Task,
+    submit_id: &str,
+    sent_at: OffsetDateTime,
+    sources: &[&SourceFile],
+    pipeline: &Pipeline,
+    sha256: String,
+) -> JournalEntry {
+    JournalEntry {
+        timestamp: sent_at,
+        task_id: task.id.clone(),
+        task_name: task.problem_name.clone(),
+        language: task.language,
+        files: sources.iter().map(|x| x.original.clone()).collect(),
+        transforms: pipeline.iter().map(|x| x.name()).collect(),
+        sha256,
+        submit_id: submit_id.to_string(),
+        snapshot: PathBuf::new(),
+    }
+}
 
-    Last::with_filter(task_id).execute(workspace, api)
+fn describe_sources(sources: &[SourceFile], rename_as: &Option<String>) -> String {
+    match (sources, rename_as) {
+        ([source], Some(new_name)) if source.name != Path::new(new_name) => {
+            format!("{} as {}", source.name.to_string_lossy(), new_name)
+        }
+        ([source], _) => source.name.to_string_lossy().to_string(),
+        _ => format!("{} files", sources.len()),
+    }
+}
+
+fn check_headers(
+    sources: &[&SourceFile],
+    language: &Language,
+    header_config: &HeaderConfig,
+    login: &str,
+) -> Result<()> {
+    let expected = header_config.expected_authors(login);
+
+    for source in sources {
+        let header = match read_header(&source.path, language)? {
+            Some(header) => header.to_lowercase(),
+            None => {
+                if sources.len() > 1 {
+                    println!("{} has no header.", source.name.to_string_lossy());
+                }
+
+                return Err(Error::NoHeader);
+            }
+        };
+
+        let is_author =
+            expected.is_empty() || expected.iter().any(|x| header.contains(&x.to_lowercase()));
+
+        if header_config.strict && !is_author {
+            if sources.len() > 1 {
+                println!(
+                    "{} has a header of someone else.",
+                    source.name.to_string_lossy()
+                );
+            }
+
+            return Err(Error::HeaderMismatch(expected.join(" or ")));
+        }
+    }
+
+    Ok(())
+}
+
+fn lint_check<W: Workspace>(
+    workspace: &W,
+    task_id: &str,
+    sources: &[&SourceFile],
+    language: &Language,
+    options: &SubmitOptions,
+) -> Result<()> {
+    let rules = LintConfig::read_config(workspace)?.rules_for(task_id);
+
+    if rules.is_empty() {
+        return Ok(());
+    }
+
+    info!("Lint rules: {:?}", rules);
+    let mut violations = Vec::new();
+
+    for source in sources {
+        violations.extend(lint_file(
+            &source.original,
+            &source.path,
+            &source.original.to_string_lossy(),
+            language,
+            &rules,
+        )?);
+    }
+
+    if violations.is_empty() {
+        return Ok(());
+    }
+
+    println!("Lint rule violations in the submitted code, code added by the transforms refers to the staged files:");
+
+    for violation in &violations {
+        println!("{}", violation.to_string().bright_red());
+    }
+
+    let e = Error::LintFailed(violations.len());
+    if options.force {
+        println!("{}", e.to_string().bright_yellow());
+        println!("{}", "Submitting anyway.".bright_yellow());
+        return Ok(());
+    }
+
+    Err(e)
+}
+
+fn compile_check<W: Workspace>(
+    workspace: &W,
+    sources: &[&SourceFile],
+    language: &Language,
+    options: &SubmitOptions,
+    build_dir: &Path,
+) -> Result<()> {
+    info!("Local compilation enabled");
+    let compiler = CompilerConfig::read_config(workspace)?.compiler_for(language)?;
+    println!("Compiling locally with {}", compiler.command);
+
+    let result = stage_for_compilation(sources, build_dir).and_then(|staged| {
+        let include_dirs = sources
+            .iter()
+            .filter_map(|x| x.original.parent())
+            .map(Path::to_path_buf)
+            .collect::<Vec<_>>();
+
+        toolchain::check(language, &compiler, &staged, &include_dirs, build_dir)
+    });
+
+    match result {
+        Err(e @ Error::Compiling(_)) if options.force => {
+            println!("{}", e.to_string().bright_yellow());
+            println!("{}", "Submitting anyway.".bright_yellow());
+            Ok(())
+        }
+        result => result,
+    }
+}
+
+/// Copies the submitted files into one tree, so they can include and refer to each other
+/// the way they will on BaCa. Returns the files to compile.
+fn stage_for_compilation(sources: &[&SourceFile], build_dir: &Path) -> Result<Vec<PathBuf>> {
+    let tree = build_dir.join("src");
+    let mut staged = Vec::new();
+
+    for source in sources {
+        let path = tree.join(&source.name);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::copy(&source.path, &path)?;
+
+        if !is_header_file(&source.name) {
+            staged.push(path);
+        }
+    }
+
+    Ok(staged)
+}
+
+/// Headers are compiled as part of the files including them.
+fn is_header_file(path: &Path) -> bool {
+    path.extension().and_then(|x| x.to_str()).is_some_and(|x| {
+        matches!(
+            x.to_lowercase().as_str(),
+            "h" | "hh" | "hpp" | "hxx" | "ads"
+        )
+    })
 }
 
 #[cfg(test)]
@@ -325,12 +602,15 @@ mod tests {
     use crate::api::baca_api::MockBacaApi;
     use crate::model;
     use crate::model::{Language, Results, Task, Tasks};
-    use crate::workspace::{ConnectionConfig, MockWorkspace};
+    use crate::workspace::workspace_dir::tests::make_temp_workspace;
+    use crate::workspace::{ConnectionConfig, LintRules, MockWorkspace, WorkspacePaths};
     use assert_fs::fixture::ChildPath;
     use assert_fs::prelude::*;
     use assert_fs::TempDir;
     use mockall::predicate::{always, eq};
     use std::fs;
+    use std::path::PathBuf;
+    use std::sync::{Arc, Mutex};
 
     pub fn make_input_file_cpp(dir: &TempDir) -> ChildPath {
         let input_file = dir.child("source.cpp");
@@ -349,12 +629,59 @@ mod tests {
         input_file
     }
 
+    /// Journal snapshots are saved in a temporary directory, removed along with the mock.
+    fn expect_journal(mock_workspace: &mut MockWorkspace) {
+        let dir = assert_fs::TempDir::new().unwrap();
+        let root = dir.to_path_buf();
+        mock_workspace.expect_get_paths().returning(move || {
+            let _ = &dir;
+            WorkspacePaths::with_root(&root)
+        });
+        mock_workspace
+            .expect_read_config_object::<Journal>()
+            .returning(|| Ok(Journal::default()));
+        mock_workspace
+            .expect_save_config_object::<Journal>()
+            .returning(|_| Ok(()));
+    }
+
+    /// Submit details report the size of the sent file, as BaCa does.
+    fn expect_submit<F>(mock_api: &mut MockBacaApi, times: usize, check: F)
+    where
+        F: Fn(&Path) + Send + 'static,
+    {
+        let sent_size = Arc::new(Mutex::new(0));
+
+        let sent_size_clone = sent_size.clone();
+        mock_api
+            .expect_submit()
+            .times(times)
+            .returning(move |_, _, file| {
+                check(Path::new(file));
+                *sent_size_clone.lock().unwrap() = fs::metadata(file).unwrap().len() as i32;
+                Ok("4242".to_string())
+            });
+        mock_api
+            .expect_get_submit_details()
+            .returning(move |_, id| {
+                Ok(model::Submit {
+                    id: id.to_string(),
+                    size: *sent_size.lock().unwrap(),
+                    ..Default::default()
+                })
+            });
+    }
+
     #[test]
     fn renamed_file_should_be_identical_to_original() {
         let mut mock_workspace = MockWorkspace::new();
         mock_workspace
             .expect_read_config_object()
             .returning(|| Ok(ConnectionConfig::default()));
+        mock_workspace
+            .expect_read_config_object::<LintConfig>()
+            .returning(|| Ok(LintConfig::default()));
+        expect_journal(&mut mock_workspace);
 
         let mut mock_api = MockBacaApi::new();
         mock_api
@@ -372,9 +699,6 @@ mod tests {
         mock_api
             .expect_get_results_by_task()
             .returning(|_, _| Ok(Results::new(vec![model::Submit::default()])));
-        mock_api
-            .expect_get_submit_details()
-            .returning(|_, _| Ok(model::Submit::default()));
 
         let dir = assert_fs::TempDir::new().unwrap();
         let original_input = make_input_file_cpp(&dir);
@@ -387,18 +711,674 @@ mod tests {
             Some("new_name.c".to_string()),
         );
 
-        mock_api.expect_submit().returning(move |_, _, file| {
+        expect_submit(&mut mock_api, 1, move |file| {
             let submitted_contents = fs::read_to_string(file).unwrap();
             let original_contents = fs::read_to_string(original_input.path()).unwrap();
             assert_eq!(submitted_contents, original_contents);
-            Ok(())
         });
 
-        submit(&mock_workspace, &mock_api, submit_config).unwrap();
+        submit(
+            &mock_workspace,
+            &mock_api,
+            submit_config,
+            &SubmitOptions::default(),
+        )
+        .unwrap();
     }
 
     // todo: test if renamed is zipped
 
+    fn make_compile_test_mocks(expected_submits: usize) -> (MockWorkspace, MockBacaApi) {
+        make_lint_test_mocks(expected_submits, LintConfig::default())
+    }
+
+    fn make_lint_test_mocks(
+        expected_submits: usize,
+        lint_config: LintConfig,
+    ) -> (MockWorkspace, MockBacaApi) {
+        let mut mock_workspace = MockWorkspace::new();
+        mock_workspace
+            .expect_read_config_object::<ConnectionConfig>()
+            .returning(|| Ok(ConnectionConfig::default()));
+        mock_workspace
+            .expect_read_config_object::<CompilerConfig>()
+            .returning(|| Err(Error::ReadingConfig("no such file".into())));
+        mock_workspace
+            .expect_read_config_object::<LintConfig>()
+            .returning(move || Ok(lint_config.clone()));
+        expect_journal(&mut mock_workspace);
+
+        let mut mock_api = MockBacaApi::new();
+        mock_api.expect_get_tasks().returning(|_| {
+            Ok(Tasks::new(vec![Task::new(
+                "1",
+                Language::Cpp,
+                "Metoda parametryzacji",
+                12,
+            )]))
+        });
+        mock_api
+            .expect_get_results_by_task()
+            .returning(|_, _| Ok(Results::new(vec![model::Submit::default()])));
+
+        match expected_submits {
+            0 => {
+                mock_api.expect_submit().never();
+            }
+            n => expect_submit(&mut mock_api, n, |_| {}),
+        }
+
+        (mock_workspace, mock_api)
+    }
+
+    fn make_broken_input_file_cpp(dir: &TempDir) -> ChildPath {
+        let input_file = dir.child("broken.cpp");
+        input_file
+            .write_str("// Hubert Jaremko\nint main() { return 0 }\n")
+            .unwrap();
+        input_file
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn given_compile_when_compilation_fails_then_do_not_submit() {
+        let (mock_workspace, mock_api) = make_compile_test_mocks(0);
+        let dir = assert_fs::TempDir::new().unwrap();
+        let input = make_broken_input_file_cpp(&dir);
+
+        let mut submit_config = SubmitConfig::new("1", input.path(), false, Language::Cpp, None);
+        submit_config.compile = true;
+
+        let result = submit(
+            &mock_workspace,
+            &mock_api,
+            submit_config,
+            &SubmitOptions::default(),
+        );
+        assert!(matches!(result, Err(Error::Compiling(_))), "{:?}", result);
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn given_compile_and_force_when_compilation_fails_then_submit() {
+        let (mock_workspace, mock_api) = make_compile_test_mocks(1);
+        let dir = assert_fs::TempDir::new().unwrap();
+        let input = make_broken_input_file_cpp(&dir);
+
+        let mut submit_config = SubmitConfig::new("1", input.path(), false, Language::Cpp, None);
+        submit_config.compile = true;
+
+        let result = submit(
+            &mock_workspace,
+            &mock_api,
+            submit_config,
+            &SubmitOptions {
+                force: true,
+                ..Default::default()
+            },
+        );
+        assert!(result.is_ok(), "{:?}", result);
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn given_compile_when_compilation_succeeds_then_submit() {
+        let (mock_workspace, mock_api) = make_compile_test_mocks(1);
+        let dir = assert_fs::TempDir::new().unwrap();
+        let input = make_input_file_cpp(&dir);
+
+        let mut submit_config = SubmitConfig::new("1", input.path(), false, Language::Cpp, None);
+        submit_config.compile = true;
+
+        let result = submit(
+            &mock_workspace,
+            &mock_api,
+            submit_config,
+            &SubmitOptions::default(),
+        );
+        assert!(result.is_ok(), "{:?}", result);
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn given_compile_when_files_include_each_other_then_submit() {
+        let (mock_workspace, mock_api) = make_compile_test_mocks(1);
+        let dir = assert_fs::TempDir::new().unwrap();
+        dir.child("source.cpp")
+            .write_str(
+                "// Hubert Jaremko\n#include \"include/matrix.h\"\nint main() { return size(); }\n",
+            )
+            .unwrap();
+        dir.child("matrix.cpp")
+            .write_str(
+                "// Hubert Jaremko\n#include \"include/matrix.h\"\nint size() { return 0; }\n",
+            )
+            .unwrap();
+        dir.child("include/matrix.h")
+            .write_str("// Hubert Jaremko\n#pragma once\nint size();\n")
+            .unwrap();
+
+        let mut submit_config = SubmitConfig::new("1", dir.path(), false, Language::Cpp, None);
+        submit_config.compile = true;
+        submit_config.strip_debug = true;
+
+        let result = submit(
+            &mock_workspace,
+            &mock_api,
+            submit_config,
+            &SubmitOptions::default(),
+        );
+        assert!(result.is_ok(), "{:?}", result);
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn given_compile_when_java_classes_refer_to_each_other_then_submit() {
+        let (mock_workspace, mock_api) = make_compile_test_mocks(1);
+        let dir = assert_fs::TempDir::new().unwrap();
+        dir.child("Main.java")
+            .write_str(
+                "// Hubert Jaremko\npublic class Main {\n    public static void main(String[] args) {\n        System.out.println(Helper.answer());\n    }\n}\n",
+            )
+            .unwrap();
+        dir.child("Helper.java")
+            .write_str(
+                "// Hubert Jaremko\npublic class Helper {\n    static int answer() {\n        return 42;\n    }\n}\n",
+            )
+            .unwrap();
+
+        let mut submit_config = SubmitConfig::new("1", dir.path(), false, Language::Java, None);
+        submit_config.compile = true;
+
+        let result = submit(
+            &mock_workspace,
+            &mock_api,
+            submit_config,
+            &SubmitOptions::default(),
+        );
+        assert!(result.is_ok(), "{:?}", result);
+    }
+
+    #[test]
+    fn given_dry_run_then_do_not_submit() {
+        let (mock_workspace, mock_api) = make_compile_test_mocks(0);
+        let dir = assert_fs::TempDir::new().unwrap();
+        let input = make_input_file_cpp(&dir);
+
+        let submit_config = SubmitConfig::new(
+            "1",
+            input.path(),
+            false,
+            Language::Cpp,
+            Some("dry_run_renamed.cpp".to_string()),
+        );
+        let options = SubmitOptions {
+            dry_run: true,
+            show_content: true,
+            show_diff: true,
+            ..Default::default()
+        };
+
+        let result = submit(&mock_workspace, &mock_api, submit_config, &options);
+        assert!(result.is_ok(), "{:?}", result);
+        assert!(input.path().exists());
+    }
+
+    #[test]
+    fn given_dry_run_then_do_not_save_config() {
+        let (mut mock_workspace, mock_api) = make_compile_test_mocks(0);
+        mock_workspace
+            .expect_read_config_object::<SubmitConfig>()
+            .returning(|| Err(Error::ReadingConfig("no such file".into())));
+        mock_workspace
+            .expect_save_config_object::<SubmitConfig>()
+            .never();
+        let dir = assert_fs::TempDir::new().unwrap();
+        let input = make_input_file_cpp(&dir);
+
+        let submit = Submit {
+            subcommand: SubmitSubcommand::None,
+            save_switch: SaveSwitch::Save,
+            provided_config: SubmitConfig::new("1", input.path(), false, Language::Cpp, None),
+            options: SubmitOptions {
+                dry_run: true,
+                ..Default::default()
+            },
+        };
+
+        let result = submit.execute(&mock_workspace, &mock_api);
+        assert!(result.is_ok(), "{:?}", result);
+    }
+
+    #[test]
+    fn staged_files_should_be_removed_after_successful_submit() {
+        let (mock_workspace, mut mock_api) = make_compile_test_mocks(0);
+        let dir = assert_fs::TempDir::new().unwrap();
+        let input = make_input_file_cpp(&dir);
+        let submitted = Arc::new(Mutex::new(PathBuf::new()));
+
+        let submitted_clone = submitted.clone();
+        expect_submit(&mut mock_api, 1, move |file| {
+            assert!(file.exists());
+            assert!(!file.starts_with(dir.path()));
+            *submitted_clone.lock().unwrap() = file.to_path_buf();
+        });
+
+        let mut submit_config = SubmitConfig::new(
+            "1",
+            input.path(),
+            true,
+            Language::Cpp,
+            Some("renamed.cpp".to_string()),
+        );
+        submit_config.no_main = true;
+        submit_config.no_polish = true;
+
+        let result = submit(
+            &mock_workspace,
+            &mock_api,
+            submit_config,
+            &SubmitOptions::default(),
+        );
+        assert!(result.is_ok(), "{:?}", result);
+
+        let submitted = submitted.lock().unwrap();
+        assert_eq!(submitted.file_name().unwrap(), "source.zip");
+        assert!(!submitted.exists());
+        assert!(!Path::new("source.zip").exists());
+    }
+
+    #[test]
+    fn given_received_size_differs_then_fail() {
+        let (mock_workspace, mut mock_api) = make_compile_test_mocks(0);
+        let dir = assert_fs::TempDir::new().unwrap();
+        let input = make_input_file_cpp(&dir);
+
+        mock_api
+            .expect_submit()
+            .times(1)
+            .returning(|_, _, _| Ok("4242".to_string()));
+        mock_api.expect_get_submit_details().returning(|_, _| {
+            Ok(model::Submit {
+                id: "4242".to_string(),
+                size: 22,
+                ..Default::default()
+            })
+        });
+
+        let mut submit_config = SubmitConfig::new("1", input.path(), true, Language::Cpp, None);
+        submit_config.no_polish = true;
+
+        let result = submit(
+            &mock_workspace,
+            &mock_api,
+            submit_config,
+            &SubmitOptions::default(),
+        );
+        assert!(
+            matches!(&result, Err(Error::UploadMismatch(id, _, 22)) if id == "4242"),
+            "{:?}",
+            result
+        );
+    }
+
+    #[test]
+    fn submit_missing_from_results_should_be_recorded_as_pending() {
+        let (_, mut mock_api) = make_compile_test_mocks(0);
+        let dir = assert_fs::TempDir::new().unwrap();
+        let input = make_input_file_cpp(&dir);
+        let paths = assert_fs::TempDir::new().unwrap();
+        let root = paths.to_path_buf();
+
+        let mut mock_workspace = MockWorkspace::new();
+        mock_workspace
+            .expect_read_config_object::<ConnectionConfig>()
+            .returning(|| Ok(ConnectionConfig::default()));
+        mock_workspace
+            .expect_read_config_object::<LintConfig>()
+            .returning(|| Ok(LintConfig::default()));
+        mock_workspace
+            .expect_get_paths()
+            .returning(move || WorkspacePaths::with_root(&root));
+        mock_workspace
+            .expect_read_config_object::<Journal>()
+            .returning(|| Ok(Journal::default()));
+        mock_workspace
+            .expect_save_config_object::<Journal>()
+            .withf(|x| x.entries.len() == 1 && x.entries[0].is_pending())
+            .times(1)
+            .returning(|_| Ok(()));
+        mock_api.checkpoint();
+        mock_api.expect_get_tasks().returning(|_| {
+            Ok(Tasks::new(vec![Task::new(
+                "1",
+                Language::Cpp,
+                "Metoda parametryzacji",
+                12,
+            )]))
+        });
+        mock_api
+            .expect_get_results_by_task()
+            .returning(|_, _| Ok(Results::new(vec![])));
+        mock_api
+            .expect_submit()
+            .times(1)
+            .returning(|_, _, _| Err(Error::SubmitNotFound));
+        mock_api.expect_get_submit_details().never();
+
+        let mut submit_config = SubmitConfig::new("1", input.path(), true, Language::Cpp, None);
+        submit_config.no_polish = true;
+
+        let result = submit(
+            &mock_workspace,
+            &mock_api,
+            submit_config,
+            &SubmitOptions::default(),
+        );
+        assert!(matches!(result, Err(Error::SubmitNotFound)), "{:?}", result);
+    }
+
+    #[test]
+    fn given_multiple_files_then_zip_them_together() {
+        let (mock_workspace, mut mock_api) = make_compile_test_mocks(0);
+        let dir = assert_fs::TempDir::new().unwrap();
+        make_input_file_cpp(&dir);
+        dir.child("include/matrix.h")
+            .write_str("// Hubert Jaremko\n#pragma once\n")
+            .unwrap();
+        dir.child("notes.txt").write_str("todo").unwrap();
+
+        expect_submit(&mut mock_api, 1, |file| {
+            let archive = zip::ZipArchive::new(fs::File::open(file).unwrap()).unwrap();
+            let mut names = archive.file_names().collect::<Vec<_>>();
+            names.sort();
+            assert_eq!(names, vec!["include/matrix.h", "source.cpp"]);
+        });
+
+        let mut submit_config = SubmitConfig::new("1", dir.path(), false, Language::Cpp, None);
+        submit_config.exclude = vec!["*.txt".to_string()];
+
+        let result = submit(
+            &mock_workspace,
+            &mock_api,
+            submit_config,
+            &SubmitOptions::default(),
+        );
+        assert!(result.is_ok(), "{:?}", result);
+    }
+
+    #[test]
+    fn given_multiple_files_when_one_has_no_header_then_do_not_submit() {
+        let (mock_workspace, mock_api) = make_compile_test_mocks(0);
+        let dir = assert_fs::TempDir::new().unwrap();
+        make_input_file_cpp(&dir);
+        dir.child("matrix.h").write_str("#pragma once\n").unwrap();
+
+        let submit_config = SubmitConfig::new("1", dir.path(), false, Language::Cpp, None);
+
+        let result = submit(
+            &mock_workspace,
+            &mock_api,
+            submit_config,
+            &SubmitOptions::default(),
+        );
+        assert!(matches!(result, Err(Error::NoHeader)), "{:?}", result);
+    }
+
+    #[test]
+    fn given_add_header_when_header_missing_then_submit_with_header() {
+        let (mock_workspace, mut mock_api) = make_compile_test_mocks(0);
+        let dir = assert_fs::TempDir::new().unwrap();
+        let input = dir.child("source.cpp");
+        input.write_str("int main() {}\n").unwrap();
+
+        expect_submit(&mut mock_api, 1, |file| {
+            assert_eq!(
+                fs::read_to_string(file).unwrap(),
+                "// Hubert Jaremko, Metoda parametryzacji\nint main() {}\n"
+            );
+        });
+
+        let mut submit_config = SubmitConfig::new("1", input.path(), false, Language::Cpp, None);
+        submit_config.header = HeaderConfig {
+            template: Some("{name}, {task}".to_string()),
+            name: Some("Hubert Jaremko".to_string()),
+            add: true,
+            strict: true,
+        };
+
+        let result = submit(
+            &mock_workspace,
+            &mock_api,
+            submit_config,
+            &SubmitOptions::default(),
+        );
+        assert!(result.is_ok(), "{:?}", result);
+    }
+
+    #[test]
+    fn given_strict_header_when_header_of_someone_else_then_do_not_submit() {
+        let (mock_workspace, mock_api) = make_compile_test_mocks(0);
+        let dir = assert_fs::TempDir::new().unwrap();
+        let input = make_input_file_cpp(&dir);
+
+        let mut submit_config = SubmitConfig::new("1", input.path(), false, Language::Cpp, None);
+        submit_config.header = HeaderConfig {
+            name: Some("Jan Kowalski".to_string()),
+            strict: true,
+            ..Default::default()
+        };
+
+        let result = submit(
+            &mock_workspace,
+            &mock_api,
+            submit_config,
+            &SubmitOptions::default(),
+        );
+        assert!(
+            matches!(&result, Err(Error::HeaderMismatch(expected)) if expected == "Jan Kowalski"),
+            "{:?}",
+            result
+        );
+    }
+
+    fn make_goto_lint_config() -> LintConfig {
+        let mut lint_config = LintConfig::default();
+        lint_config.tasks.insert(
+            "1".to_string(),
+            LintRules {
+                forbidden_identifiers: vec!["goto".to_string()],
+                ..Default::default()
+            },
+        );
+        lint_config
+    }
+
+    #[test]
+    fn given_lint_violation_then_do_not_submit() {
+        let (mock_workspace, mock_api) = make_lint_test_mocks(0, make_goto_lint_config());
+        let dir = assert_fs::TempDir::new().unwrap();
+        let input = dir.child("source.cpp");
+        input
+            .write_str("// Hubert Jaremko\nint main() { goto end; end: return 0; }\n")
+            .unwrap();
+
+        let submit_config = SubmitConfig::new("1", input.path(), false, Language::Cpp, None);
+
+        let result = submit(
+            &mock_workspace,
+            &mock_api,
+            submit_config,
+            &SubmitOptions::default(),
+        );
+        assert!(matches!(result, Err(Error::LintFailed(1))), "{:?}", result);
+    }
+
+    #[test]
+    fn given_forbidden_include_in_inlined_header_then_do_not_submit() {
+        let mut lint_config = LintConfig::default();
+        lint_config.tasks.insert(
+            "1".to_string(),
+            LintRules {
+                forbidden_includes: vec!["vector".to_string()],
+                ..Default::default()
+            },
+        );
+        let (mock_workspace, mock_api) = make_lint_test_mocks(0, lint_config);
+        let dir = assert_fs::TempDir::new().unwrap();
+        let input = dir.child("source.cpp");
+        input
+            .write_str("// Hubert Jaremko\n#include \"local.h\"\nint main() {}\n")
+            .unwrap();
+        dir.child("local.h")
+            .write_str("#include <vector>\n")
+            .unwrap();
+
+        let mut submit_config = SubmitConfig::new("1", input.path(), false, Language::Cpp, None);
+        submit_config.inline_includes = true;
+
+        let result = submit(
+            &mock_workspace,
+            &mock_api,
+            submit_config,
+            &SubmitOptions::default(),
+        );
+        assert!(matches!(result, Err(Error::LintFailed(1))), "{:?}", result);
+    }
+
+    #[test]
+    fn given_lint_violation_and_force_then_submit() {
+        let (mock_workspace, mock_api) = make_lint_test_mocks(1, make_goto_lint_config());
+        let dir = assert_fs::TempDir::new().unwrap();
+        let input = dir.child("source.cpp");
+        input
+            .write_str("// Hubert Jaremko\nint main() { goto end; end: return 0; }\n")
+            .unwrap();
+
+        let submit_config = SubmitConfig::new("1", input.path(), false, Language::Cpp, None);
+        let options = SubmitOptions {
+            force: true,
+            ..Default::default()
+        };
+
+        let result = submit(&mock_workspace, &mock_api, submit_config, &options);
+        assert!(result.is_ok(), "{:?}", result);
+    }
+
+    #[test]
+    fn given_successful_submit_then_record_it_in_journal() {
+        let (temp_dir, mock_paths, workspace) = make_temp_workspace().unwrap();
+        workspace.initialize().unwrap();
+        ConnectionConfig::default().save_config(&workspace).unwrap();
+        let input = make_input_file_cpp(&temp_dir);
+
+        let mut mock_api = MockBacaApi::new();
+        mock_api.expect_get_tasks().returning(|_| {
+            Ok(Tasks::new(vec![Task::new(
+                "1",
+                Language::Cpp,
+                "Metoda SOR",
+                12,
+            )]))
+        });
+        expect_submit(&mut mock_api, 1, |_| {});
+        mock_api
+            .expect_get_results_by_task()
+            .returning(|_, _| Ok(Results::default()));
+
+        let mut submit_config = SubmitConfig::new("1", input.path(), true, Language::Cpp, None);
+        submit_config.no_polish = true;
+
+        let result = submit(
+            &workspace,
+            &mock_api,
+            submit_config,
+            &SubmitOptions::default(),
+        );
+        assert!(result.is_ok(), "{:?}", result);
+
+        let journal = Journal::read_config(&workspace).unwrap();
+        assert_eq!(journal.entries.len(), 1);
+        let entry = &journal.entries[0];
+        assert_eq!(entry.task_id, "1");
+        assert_eq!(entry.task_name, "Metoda SOR");
+        assert_eq!(entry.submit_id, "4242");
+        assert_eq!(entry.files, vec![input.to_path_buf()]);
+        assert_eq!(entry.transforms, vec!["no-polish", "zip"]);
+        assert_eq!(
+            entry.snapshot,
+            mock_paths.submissions_dir().join("4242/source.zip")
+        );
+        assert_eq!(journal::hash_file(&entry.snapshot).unwrap(), entry.sha256);
+    }
+
+    #[test]
+    fn given_allow_duplicate_when_file_already_submitted_then_submit_again() {
+        let (temp_dir, _, workspace) = make_temp_workspace().unwrap();
+        workspace.initialize().unwrap();
+        ConnectionConfig::default().save_config(&workspace).unwrap();
+        let input = make_input_file_cpp(&temp_dir);
+
+        let mut mock_api = MockBacaApi::new();
+        mock_api.expect_get_tasks().returning(|_| {
+            Ok(Tasks::new(vec![Task::new(
+                "1",
+                Language::Cpp,
+                "Metoda SOR",
+                12,
+            )]))
+        });
+        expect_submit(&mut mock_api, 2, |_| {});
+        mock_api
+            .expect_get_results_by_task()
+            .returning(|_, _| Ok(Results::default()));
+
+        let submit_config = SubmitConfig::new("1", input.path(), false, Language::Cpp, None);
+        let options = SubmitOptions {
+            allow_duplicate: true,
+            ..Default::default()
+        };
+
+        for _ in 0..2 {
+            let result = submit(&workspace, &mock_api, submit_config.clone(), &options);
+            assert!(result.is_ok(), "{:?}", result);
+        }
+
+        let journal = Journal::read_config(&workspace).unwrap();
+        assert_eq!(journal.entries.len(), 2);
+        assert_eq!(journal.entries[0].sha256, journal.entries[1].sha256);
+    }
+
+    #[test]
+    fn given_multiple_files_when_renaming_then_fail() {
+        let (mock_workspace, mock_api) = make_compile_test_mocks(0);
+        let dir = assert_fs::TempDir::new().unwrap();
+        make_input_file_cpp(&dir);
+        dir.child("other.cpp")
+            .write_str("// Hubert Jaremko\n")
+            .unwrap();
+
+        let submit_config = SubmitConfig::new(
+            "1",
+            dir.path(),
+            false,
+            Language::Cpp,
+            Some("renamed.cpp".to_string()),
+        );
+
+        let result = submit(
+            &mock_workspace,
+            &mock_api,
+            submit_config,
+            &SubmitOptions::default(),
+        );
+        assert!(
+            matches!(result, Err(Error::RenamingMultipleFiles)),
+            "{:?}",
+            result
+        );
+    }
+
     #[test]
     fn fetch_allowed_language_test_success() {
         let mut mock_workspace = MockWorkspace::new();
@@ -34,6 +34,34 @@ pub enum Error {
     SubmitArgumentNotProvided(String),
     InputFileDoesNotExist,
     NoHeader,
+    HeaderMismatch(String),
+    LintFailed(usize),
+    DuplicateSubmit(String),
+    SubmitNotFound,
+    ProfileNotFound(String),
+    PasswordCommandFailed(String, String),
+    WrongPassphrase,
+    SecretsCorrupted,
+    EncryptionNotSetUp,
+    NoRandomSource(String),
+    NotRecorded(String),
+    NotInteractive(String),
+    UploadMismatch(String, u64, i32),
+    NoSourceFiles,
+    DuplicateSourceName(String),
+    InvalidPattern(String),
+    RenamingMultipleFiles,
+    InvalidTransforms(String),
+    IncludeCycle(String),
+    UnmatchedStripMarker(String, usize),
+    MainNotFound,
+    UnknownEncoding(String),
+    InvalidEncoding(String, String),
+    TransformFailed(String, String),
+    Compiling(String),
+    CompilerNotFound(String),
+    StressProgramFailed(String),
+    StressTestFailed(String),
 }
 
 impl std::error::Error for Error {}
@@ -69,6 +97,34 @@ impl fmt::Display for Error {
             Error::EditorFail(code) => format!("Config editor failed with exit code: {}", code),
             Error::SubmitArgumentNotProvided(argument) => format!("Please provide a {}. Type 'baca submit -h' for more info.", argument),
             Error::NoHeader => "No header!".to_owned(),
+            Error::LintFailed(count) => format!("Found {} lint rule violations.", count),
+            Error::DuplicateSubmit(id) => format!("Not submitted, the same file was already sent as submit {}. Use '--allow-duplicate' to send it anyway.", id),
+            Error::SubmitNotFound => "Submit was sent, but it does not show up in the results yet. Check 'baca log' in a moment.".to_owned(),
+            Error::UploadMismatch(id, sent, received) => format!("Submit {} has {} bytes instead of {}, it is not the prepared file! Check it with 'baca details {}' and submit again.", id, received, sent, id),
+            Error::ProfileNotFound(name) => format!("Profile '{}' does not exist.", name),
+            Error::PasswordCommandFailed(command, e) => format!("Password command '{}' failed: {}", command, e),
+            Error::WrongPassphrase => "Wrong passphrase.".to_owned(),
+            Error::SecretsCorrupted => "Cannot decrypt the workspace secrets, they are corrupted. Run 'baca init' again.".to_owned(),
+            Error::EncryptionNotSetUp => "The workspace secrets are encrypted, but the encryption is missing from the user config. Run 'baca init' again.".to_owned(),
+            Error::NoRandomSource(e) => format!("Cannot get random data from the operating system: {}", e),
+            Error::NotRecorded(request) => format!("No recorded response to {}, record it again with BACA_RECORD.", request),
+            Error::NotInteractive(argument) => format!("Please provide {}, prompts are disabled in non-interactive mode.", argument),
+            Error::HeaderMismatch(expected) => format!("Header does not contain {}.", expected),
+            Error::NoSourceFiles => "No files to submit, check the provided paths and patterns.".to_owned(),
+            Error::DuplicateSourceName(name) => format!("More than one selected file would be submitted as {}.", name),
+            Error::InvalidPattern(pattern) => format!("Invalid pattern: {}", pattern),
+            Error::RenamingMultipleFiles => "Only a single file can be renamed.".to_owned(),
+            Error::InvalidTransforms(reason) => format!("Invalid transforms: {}.", reason),
+            Error::IncludeCycle(cycle) => format!("Include cycle detected: {}", cycle),
+            Error::UnmatchedStripMarker(marker, line) => format!("Unmatched '{}' in line {}.", marker, line),
+            Error::UnknownEncoding(encoding) => format!("Unknown encoding '{}'.", encoding),
+            Error::InvalidEncoding(file, encoding) => format!("{} is not valid {}, declare its encoding, e.g. '--encoding windows-1250'.", file, encoding),
+            Error::MainNotFound => "No main function found, nothing to remove. Submit without main removal or check the file.".to_owned(),
+            Error::TransformFailed(command, output) => format!("Transform '{}' failed:\n{}", command, output),
+            Error::Compiling(output) => format!("Compilation failed:\n{}", output),
+            Error::CompilerNotFound(compiler) => format!("Compiler '{}' not found. Make sure it is installed and available in PATH.", compiler),
+            Error::StressProgramFailed(program) => format!("{} did not finish successfully, fix it before stress testing.", program),
+            Error::StressTestFailed(path) => format!("Stress test failed! Failing input saved as {}", path),
         };
 
         write!(f, "{}", msg)
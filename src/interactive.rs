@@ -0,0 +1,41 @@
+use crate::error::{Error, Result};
+use std::sync::atomic::{AtomicU8, Ordering};
+use tracing::debug;
+
+/// How prompts are answered.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mode {
+    /// Asks the user.
+    Interactive,
+    /// Set by `--non-interactive` or when stdin is not a terminal, uses the defaults.
+    Defaults,
+    /// Set by `--yes`, like `Defaults` but answers every confirmation with yes.
+    AssumeYes,
+}
+
+static MODE: AtomicU8 = AtomicU8::new(Mode::Interactive as u8);
+
+pub fn set_mode(mode: Mode) {
+    debug!("Prompt mode: {:?}", mode);
+    MODE.store(mode as u8, Ordering::Relaxed);
+}
+
+pub fn mode() -> Mode {
+    match MODE.load(Ordering::Relaxed) {
+        x if x == Mode::Defaults as u8 => Mode::Defaults,
+        x if x == Mode::AssumeYes as u8 => Mode::AssumeYes,
+        _ => Mode::Interactive,
+    }
+}
+
+/// Fails instead of prompting when prompts are disabled, `argument` tells how to provide the value.
+pub fn require(argument: &str) -> Result<()> {
+    require_in(mode(), argument)
+}
+
+pub fn require_in(mode: Mode, argument: &str) -> Result<()> {
+    match mode {
+        Mode::Interactive => Ok(()),
+        Mode::Defaults | Mode::AssumeYes => Err(Error::NotInteractive(argument.to_string())),
+    }
+}
@@ -1,11 +1,125 @@
+use regex::Regex;
+use std::io;
+use std::io::Write;
+use std::sync::{Mutex, OnceLock};
 use tracing::Level;
 use tracing_subscriber::FmtSubscriber;
 
+const MASK: &str = "***";
+
+/// Shorter values would mask unrelated text.
+const MIN_SECRET_LEN: usize = 3;
+
+/// Passwords and cookies seen so far, replaced in all log output.
+static SECRETS: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
 pub fn init_logging(level: Level) {
     let subscriber = FmtSubscriber::builder()
         .without_time()
         .with_max_level(level)
+        .with_writer(|| RedactingWriter(io::stdout()))
         .finish();
     tracing::subscriber::set_global_default(subscriber).expect("setting default subscriber failed");
     tracing::debug!("Log level: {}", level);
 }
+
+/// Makes sure `secret` never shows up in the logs.
+pub fn register_secret(secret: &str) {
+    if secret.len() < MIN_SECRET_LEN {
+        return;
+    }
+
+    let mut secrets = SECRETS.lock().unwrap();
+    if !secrets.iter().any(|x| x == secret) {
+        secrets.push(secret.to_string());
+        // Longer first, so a secret containing another one is masked whole.
+        secrets.sort_by_key(|x| std::cmp::Reverse(x.len()));
+    }
+}
+
+/// Replaces the registered secrets and session cookies with `***`.
+pub fn redact(text: &str) -> String {
+    static COOKIE: OnceLock<Regex> = OnceLock::new();
+    let cookie = COOKIE.get_or_init(|| Regex::new(r#"JSESSIONID=[^;\s"'\\]*"#).unwrap());
+
+    let mut text = cookie
+        .replace_all(text, format!("JSESSIONID={}", MASK))
+        .to_string();
+
+    for secret in SECRETS.lock().unwrap().iter() {
+        text = text.replace(secret, MASK);
+    }
+
+    text
+}
+
+/// Masks secrets in `value` for `Debug` output, keeping empty values visible.
+pub fn mask(value: &str) -> &str {
+    match value.is_empty() {
+        true => value,
+        false => MASK,
+    }
+}
+
+/// Redacts every formatted event before it reaches the output.
+struct RedactingWriter<W: Write>(W);
+
+impl<W: Write> Write for RedactingWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let text = String::from_utf8_lossy(buf);
+        self.0.write_all(redact(&text).as_bytes())?;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.0.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn registered_secrets_should_be_masked() {
+        register_secret("PaSsWorD-log-test");
+
+        let redacted = redact("7|0|7|login|PaSsWorD-log-test|1|2|");
+
+        assert_eq!(redacted, "7|0|7|login|***|1|2|");
+    }
+
+    #[test]
+    fn session_cookies_should_be_masked() {
+        let redacted = redact(
+            r#"{"cookie": "JSESSIONID=A1B2C3D4;", "set-cookie": "JSESSIONID=E5F6; Path=/"}"#,
+        );
+
+        assert_eq!(
+            redacted,
+            r#"{"cookie": "JSESSIONID=***;", "set-cookie": "JSESSIONID=***; Path=/"}"#
+        );
+    }
+
+    #[test]
+    fn short_secrets_should_not_be_registered() {
+        register_secret("a");
+
+        assert_eq!(redact("baca"), "baca");
+    }
+
+    #[test]
+    fn writer_should_redact_output() {
+        register_secret("cookie-writer-test");
+        let mut writer = RedactingWriter(Vec::new());
+
+        writer
+            .write_all(b"DEBUG Cookie: JSESSIONID = cookie-writer-test\n")
+            .unwrap();
+
+        assert_eq!(
+            String::from_utf8(writer.0).unwrap(),
+            "DEBUG Cookie: JSESSIONID = ***\n"
+        );
+    }
+}
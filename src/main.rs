@@ -1,32 +1,56 @@
-use crate::cli::Cli;
+use crate::api::baca_api::BacaApi;
+use crate::cli::{Cli, Commands};
+use crate::interactive::Mode;
 use crate::update::{GithubReleases, UpdateCheckTimestamp, UpdateChecker, UpdateStatus};
-use crate::workspace::{ConfigObject, WorkspaceDir};
+use crate::workspace::workspace_paths::user_config_dir;
+use crate::workspace::{
+    ConfigObject, ConnectionConfig, ConnectionOverrides, CredentialProvider, MemoryWorkspace,
+    UserConfig, Workspace, WorkspaceDir, WorkspacePaths,
+};
 use api::baca_service::BacaService;
+use api::replay_service::ReplayService;
+use api::traffic;
 use clap::Parser;
 use colored::Colorize;
 use std::env;
+use std::io::{stdin, IsTerminal};
+use std::path::PathBuf;
 use tracing::{error, info, Level};
 
 mod api;
 mod cli;
 mod command;
 mod error;
+mod interactive;
 mod log;
 mod model;
 mod parse;
+mod toolchain;
+mod transform;
 mod update;
 mod workspace;
 
 fn main() {
     let cli = Cli::parse();
-    let workspace = WorkspaceDir::new();
-    let baca_api = BacaService::default();
+    let workspace = make_workspace(&cli);
 
     set_logging_level(&cli);
-    check_for_updates(&workspace, cli.no_update, cli.force_update);
+    interactive::set_mode(prompt_mode(&cli));
+    capture_traffic(&cli, &workspace);
+    let no_update = cli.no_update || profile_disables_updates(&workspace);
+    check_for_updates(&workspace, no_update, cli.force_update);
+    encrypt_plaintext_secrets(&workspace);
+
+    let overrides = connection_overrides(&cli);
+    let in_memory = runs_in_memory(&cli, &workspace, &overrides);
+    overrides.activate();
 
     let result = match &cli.command {
-        Some(commands) => command::execute(&workspace, &baca_api, commands),
+        Some(commands) => match env::var_os("BACA_REPLAY") {
+            Some(dir) => ReplayService::new(&PathBuf::from(dir))
+                .and_then(|api| run(&workspace, &api, commands, in_memory)),
+            None => run(&workspace, &BacaService::default(), commands, in_memory),
+        },
         None => Ok(()),
     };
 
@@ -36,6 +60,138 @@ fn main() {
     }
 }
 
+/// `init` creates the workspace in the current directory, even inside another workspace.
+fn make_workspace(cli: &Cli) -> WorkspaceDir {
+    let root = cli
+        .workspace
+        .clone()
+        .or_else(|| env::var_os("BACA_WORKSPACE").map(PathBuf::from));
+
+    match root {
+        Some(root) => WorkspaceDir::with_paths(
+            WorkspacePaths::with_root(&root).with_user_dir(user_config_dir()),
+        ),
+        None if initializing(cli) => WorkspaceDir::with_paths(WorkspacePaths::current()),
+        None => WorkspaceDir::new(),
+    }
+}
+
+/// Options take precedence over the `BACA_*` variables, except for `BACA_PASSWORD`,
+/// as `--password` shows up in the process list.
+fn connection_overrides(cli: &Cli) -> ConnectionOverrides {
+    let var = |name| env::var(name).ok().filter(|x: &String| !x.is_empty());
+
+    ConnectionOverrides {
+        host: cli.host.clone().or_else(|| var("BACA_HOST")),
+        login: cli.login.clone().or_else(|| var("BACA_LOGIN")),
+        password: var("BACA_PASSWORD").or_else(|| cli.password.clone()),
+        cookie: cli.cookie.clone().or_else(|| var("BACA_COOKIE")),
+    }
+}
+
+/// Without a `.baca` directory, a command can still run statelessly when the host is given.
+fn runs_in_memory(cli: &Cli, workspace: &WorkspaceDir, overrides: &ConnectionOverrides) -> bool {
+    !initializing(cli) && overrides.host.is_some() && !workspace.get_paths().baca_dir().is_dir()
+}
+
+fn initializing(cli: &Cli) -> bool {
+    matches!(cli.command, Some(Commands::Init { .. }))
+}
+
+fn prompt_mode(cli: &Cli) -> Mode {
+    if cli.yes {
+        Mode::AssumeYes
+    } else if cli.non_interactive || !stdin().is_terminal() {
+        Mode::Defaults
+    } else {
+        Mode::Interactive
+    }
+}
+
+fn run<A: BacaApi>(
+    workspace: &WorkspaceDir,
+    api: &A,
+    commands: &Commands,
+    in_memory: bool,
+) -> error::Result<()> {
+    if !in_memory {
+        return command::execute(workspace, api, commands);
+    }
+
+    let workspace = MemoryWorkspace::new();
+    workspace.save_config_object(&ConnectionConfig {
+        permutation: api::details::permutation(),
+        ..Default::default()
+    })?;
+
+    let mut config = ConnectionConfig::read_config(&workspace)?;
+    if config.cookie.is_empty() && needs_session(commands) {
+        info!("No session cookie given, logging in.");
+        if config.credential_provider() == CredentialProvider::Prompt {
+            interactive::require("the password with BACA_PASSWORD")?;
+        }
+        config.cookie = api.get_cookie(&config)?;
+        config.save_config(&workspace)?;
+    }
+
+    command::execute(&workspace, api, commands)
+}
+
+fn needs_session(commands: &Commands) -> bool {
+    matches!(
+        commands,
+        Commands::Details { .. }
+            | Commands::Log { .. }
+            | Commands::Tasks {}
+            | Commands::Submit { .. }
+            | Commands::Last { .. }
+            | Commands::DebugReport { .. }
+    )
+}
+
+fn profile_disables_updates(workspace: &WorkspaceDir) -> bool {
+    let profile = workspace
+        .read_config_object::<ConnectionConfig>()
+        .ok()
+        .and_then(|x| x.profile);
+
+    UserConfig::read_config(workspace)
+        .ok()
+        .and_then(|x| x.active_profile(profile.as_deref()).cloned())
+        .is_some_and(|x| x.no_update)
+}
+
+/// Keeps the raw requests for `baca debug-report` at trace level and all of them with `BACA_RECORD`.
+fn capture_traffic(cli: &Cli, workspace: &WorkspaceDir) {
+    let paths = workspace.get_paths();
+
+    if let Some(dir) = env::var_os("BACA_RECORD") {
+        traffic::record_to(PathBuf::from(dir));
+    }
+
+    if cli.verbose >= 3 && paths.baca_dir().is_dir() {
+        traffic::capture_to(paths.traffic_dir());
+    }
+}
+
+fn encrypt_plaintext_secrets(workspace: &WorkspaceDir) {
+    let encrypted = UserConfig::encrypt_plaintext(workspace).and_then(|profiles| {
+        ConnectionConfig::encrypt_plaintext(workspace).map(|connection| profiles || connection)
+    });
+
+    match encrypted {
+        Ok(true) => println!("{}", "Saved credentials are now encrypted.".bright_green()),
+        Ok(false) => {}
+        Err(e) => {
+            error!("Error encrypting credentials: {:?}", e);
+            println!(
+                "{}",
+                format!("Credentials were not encrypted: {}", e).bright_red()
+            );
+        }
+    }
+}
+
 fn set_logging_level(cli: &Cli) {
     let log_level = match cli.verbose {
         0 => return,
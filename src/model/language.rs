@@ -1,5 +1,6 @@
 use crate::error;
 use serde::{Deserialize, Serialize};
+use std::path::Path;
 use std::str::FromStr;
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
@@ -30,12 +31,36 @@ impl Language {
         }
     }
 
+    /// Opening and closing sequence of a block comment, accepted as a header as well.
+    pub fn block_comment_style(&self) -> Option<(&str, &str)> {
+        match self {
+            Language::Cpp | Language::CppWithFileSupport | Language::Java => Some(("/*", "*/")),
+            _ => None,
+        }
+    }
+
     pub fn is_comment(&self, line: &str) -> bool {
         match self.comment_styles() {
             None => false,
             Some(comment_styles) => comment_styles.iter().any(|style| line.starts_with(style)),
         }
     }
+
+    pub fn from_extension(extension: &str) -> Option<Self> {
+        match extension.to_lowercase().as_str() {
+            "cpp" | "cc" | "cxx" | "c++" | "c" | "h" | "hpp" => Some(Language::Cpp),
+            "java" => Some(Language::Java),
+            "sh" | "bash" => Some(Language::Bash),
+            "adb" | "ads" | "ada" => Some(Language::Ada),
+            _ => None,
+        }
+    }
+
+    pub fn from_path(path: &Path) -> Option<Self> {
+        path.extension()
+            .and_then(|ext| ext.to_str())
+            .and_then(Self::from_extension)
+    }
 }
 
 impl ToString for Language {
@@ -169,4 +194,26 @@ mod tests {
     fn unsupported_comment() {
         assert!(!Language::Unsupported.is_comment("% Hubert Jaremko"));
     }
+
+    #[test]
+    fn from_path() {
+        assert_eq!(
+            Language::from_path(Path::new("gen.cpp")),
+            Some(Language::Cpp)
+        );
+        assert_eq!(
+            Language::from_path(Path::new("a/Main.java")),
+            Some(Language::Java)
+        );
+        assert_eq!(
+            Language::from_path(Path::new("brute.SH")),
+            Some(Language::Bash)
+        );
+        assert_eq!(
+            Language::from_path(Path::new("main.adb")),
+            Some(Language::Ada)
+        );
+        assert_eq!(Language::from_path(Path::new("notes.txt")), None);
+        assert_eq!(Language::from_path(Path::new("Makefile")), None);
+    }
 }
@@ -1,5 +1,5 @@
 use crate::model::submit_status::SubmitStatus;
-use crate::model::TestResults;
+use crate::model::{Language, TestResults};
 use colored::*;
 
 #[derive(Debug, PartialEq, Clone, Default)]
@@ -20,6 +20,11 @@ pub struct Submit {
 }
 
 impl Submit {
+    /// BaCa reports the language by its Polish name, so it is parsed rather than compared as text.
+    pub fn has_language(&self, language: Language) -> bool {
+        self.language.parse::<Language>().ok() == Some(language)
+    }
+
     // todo: ctor
     pub fn print_with_tests(&self) {
         self.print();
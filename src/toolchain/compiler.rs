@@ -0,0 +1,261 @@
+use crate::error::{Error, Result};
+use crate::model::Language;
+use crate::toolchain::Program;
+use serde::{Deserialize, Serialize};
+use std::io::ErrorKind;
+use std::path::Path;
+use std::process;
+use tracing::{debug, info};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Compiler {
+    pub command: String,
+    pub flags: Vec<String>,
+}
+
+impl Compiler {
+    pub fn new(command: &str, flags: &[&str]) -> Self {
+        Self {
+            command: command.to_string(),
+            flags: flags.iter().map(|x| x.to_string()).collect(),
+        }
+    }
+
+    pub fn default_for(language: &Language) -> Option<Self> {
+        let compiler = match language {
+            Language::Cpp | Language::CppWithFileSupport => {
+                Self::new("g++", &["-std=c++17", "-O2"])
+            }
+            Language::Java => Self::new("javac", &[]),
+            Language::Ada => Self::new("gnatmake", &["-q"]),
+            Language::Bash => Self::new("bash", &["-n"]),
+            Language::Unsupported => return None,
+        };
+
+        Some(compiler)
+    }
+
+    fn command(&self) -> process::Command {
+        let mut cmd = process::Command::new(&self.command);
+        cmd.args(&self.flags);
+        cmd
+    }
+}
+
+/// Compiles `source` into `build_dir` and returns a program ready to run.
+/// Interpreted languages are only syntax-checked.
+pub fn compile(
+    language: &Language,
+    compiler: &Compiler,
+    source: &Path,
+    build_dir: &Path,
+) -> Result<Program> {
+    let stem = file_stem(source)?;
+    let output = build_dir.join(stem);
+    let mut cmd = compiler.command();
+
+    let program = match language {
+        Language::Cpp | Language::CppWithFileSupport => {
+            cmd.arg(source).arg("-o").arg(&output);
+            Program::new(&output, &[])
+        }
+        Language::Java => {
+            cmd.arg("-d").arg(&output).arg(source);
+            Program::new("java", &["-cp".as_ref(), output.as_os_str(), stem.as_ref()])
+        }
+        Language::Ada => {
+            cmd.arg("-D")
+                .arg(build_dir)
+                .arg(source)
+                .arg("-o")
+                .arg(&output);
+            Program::new(&output, &[])
+        }
+        Language::Bash => {
+            cmd.arg(source);
+            Program::new("bash", &[source.as_os_str()])
+        }
+        Language::Unsupported => {
+            return Err(Error::UnsupportedLanguage(language.to_string()));
+        }
+    };
+
+    run_compiler(cmd, compiler, source)?;
+    Ok(program)
+}
+
+/// Compiles `sources` without linking, so files submitted without `main` can be checked as well.
+///
+/// The sources are compiled together, local headers are also looked up in `include_dirs`.
+pub fn check<P: AsRef<Path>>(
+    language: &Language,
+    compiler: &Compiler,
+    sources: &[P],
+    include_dirs: &[P],
+    build_dir: &Path,
+) -> Result<()> {
+    let includes = include_dirs
+        .iter()
+        .map(|x| format!("-I{}", x.as_ref().to_string_lossy()))
+        .collect::<Vec<_>>();
+
+    // Java classes refer to each other, so they are compiled in a single run.
+    if *language == Language::Java {
+        let mut cmd = compiler.command();
+        cmd.arg("-d").arg(build_dir.join("classes"));
+        cmd.args(sources.iter().map(|x| x.as_ref()));
+        return match sources.first() {
+            Some(source) => run_compiler(cmd, compiler, source.as_ref()),
+            None => Ok(()),
+        };
+    }
+
+    for (number, source) in sources.iter().enumerate() {
+        let source = source.as_ref();
+        let output = build_dir.join(format!("{}-{}", number, file_stem(source)?));
+        let mut cmd = compiler.command();
+
+        match language {
+            Language::Cpp | Language::CppWithFileSupport => {
+                cmd.args(&includes)
+                    .arg("-c")
+                    .arg(source)
+                    .arg("-o")
+                    .arg(output.with_extension("o"));
+            }
+            Language::Ada => {
+                cmd.args(&includes)
+                    .arg("-c")
+                    .arg("-D")
+                    .arg(build_dir)
+                    .arg(source);
+            }
+            Language::Bash => {
+                cmd.arg(source);
+            }
+            Language::Java | Language::Unsupported => {
+                return Err(Error::UnsupportedLanguage(language.to_string()));
+            }
+        };
+
+        run_compiler(cmd, compiler, source)?;
+    }
+
+    Ok(())
+}
+
+fn file_stem(source: &Path) -> Result<&str> {
+    source
+        .file_stem()
+        .and_then(|x| x.to_str())
+        .ok_or(Error::InputFileDoesNotExist)
+}
+
+fn run_compiler(mut cmd: process::Command, compiler: &Compiler, source: &Path) -> Result<()> {
+    info!("Compiling {:?} with {}", source, compiler.command);
+    debug!("{:?}", cmd);
+
+    let result = cmd.output().map_err(|e| match e.kind() {
+        ErrorKind::NotFound => Error::CompilerNotFound(compiler.command.clone()),
+        _ => e.into(),
+    })?;
+
+    if !result.status.success() {
+        let mut message = String::from_utf8_lossy(&result.stderr).to_string();
+        message.push_str(&String::from_utf8_lossy(&result.stdout));
+        return Err(Error::Compiling(message));
+    }
+
+    Ok(())
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use crate::toolchain::RunStatus;
+    use assert_fs::prelude::*;
+    use std::time::Duration;
+
+    #[test]
+    fn unsupported_language_has_no_compiler() {
+        assert!(Compiler::default_for(&Language::Unsupported).is_none());
+    }
+
+    #[test]
+    fn bash_should_be_syntax_checked() {
+        let dir = assert_fs::TempDir::new().unwrap();
+        let source = dir.child("broken.sh");
+        source.write_str("if then fi fi\n").unwrap();
+
+        let compiler = Compiler::default_for(&Language::Bash).unwrap();
+        let result = compile(&Language::Bash, &compiler, source.path(), dir.path());
+
+        assert!(matches!(result, Err(Error::Compiling(_))), "{:?}", result);
+    }
+
+    #[test]
+    fn compiled_bash_should_run() {
+        let dir = assert_fs::TempDir::new().unwrap();
+        let source = dir.child("echo.sh");
+        source.write_str("read x\necho $((x * 2))\n").unwrap();
+        let input = dir.child("input.txt");
+        input.write_str("21\n").unwrap();
+
+        let compiler = Compiler::default_for(&Language::Bash).unwrap();
+        let program = compile(&Language::Bash, &compiler, source.path(), dir.path()).unwrap();
+        let output = program
+            .run(&[], Some(input.path()), Duration::from_secs(5))
+            .unwrap();
+
+        assert_eq!(output.status, RunStatus::Exited);
+        assert_eq!(output.stdout, "42\n");
+    }
+
+    #[test]
+    fn check_should_not_require_main() {
+        let dir = assert_fs::TempDir::new().unwrap();
+        let source = dir.child("no_main.cpp");
+        source
+            .write_str("int add(int a, int b) { return a + b; }")
+            .unwrap();
+
+        let compiler = Compiler::default_for(&Language::Cpp).unwrap();
+        let result = check(&Language::Cpp, &compiler, &[source.path()], &[], dir.path());
+
+        assert!(result.is_ok(), "{:?}", result);
+    }
+
+    #[test]
+    fn check_should_report_compiler_output() {
+        let dir = assert_fs::TempDir::new().unwrap();
+        let source = dir.child("broken.cpp");
+        source
+            .write_str("int add(int a, int b) { return a + ; }")
+            .unwrap();
+
+        let compiler = Compiler::default_for(&Language::Cpp).unwrap();
+        let result = check(&Language::Cpp, &compiler, &[source.path()], &[], dir.path());
+
+        assert!(
+            matches!(&result, Err(Error::Compiling(output)) if output.contains("broken.cpp")),
+            "{:?}",
+            result
+        );
+    }
+
+    #[test]
+    fn missing_compiler_should_be_reported() {
+        let dir = assert_fs::TempDir::new().unwrap();
+        let source = dir.child("main.cpp");
+        source.write_str("int main() {}").unwrap();
+
+        let compiler = Compiler::new("surely-not-a-compiler", &[]);
+        let result = compile(&Language::Cpp, &compiler, source.path(), dir.path());
+
+        assert!(
+            matches!(result, Err(Error::CompilerNotFound(_))),
+            "{:?}",
+            result
+        );
+    }
+}
@@ -0,0 +1,109 @@
+use crate::error::{Error, Result};
+use crate::model::Language;
+use crate::toolchain::Compiler;
+use crate::workspace::{ConfigObject, Workspace};
+use serde::{Deserialize, Serialize};
+
+/// Per-language compiler overrides, languages not listed here use `Compiler::default_for`.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CompilerConfig {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cpp: Option<Compiler>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub java: Option<Compiler>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bash: Option<Compiler>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ada: Option<Compiler>,
+}
+
+impl CompilerConfig {
+    pub fn compiler_for(&self, language: &Language) -> Result<Compiler> {
+        let configured = match language {
+            Language::Cpp | Language::CppWithFileSupport => &self.cpp,
+            Language::Java => &self.java,
+            Language::Bash => &self.bash,
+            Language::Ada => &self.ada,
+            Language::Unsupported => &None,
+        };
+
+        configured
+            .clone()
+            .or_else(|| Compiler::default_for(language))
+            .ok_or_else(|| Error::UnsupportedLanguage(language.to_string()))
+    }
+}
+
+impl ConfigObject for CompilerConfig {
+    fn save_config<W: Workspace>(&self, workspace: &W) -> Result<()> {
+        workspace.save_config_object(self)
+    }
+
+    fn read_config<W: Workspace>(workspace: &W) -> Result<Self> {
+        match workspace.read_config_object::<Self>() {
+            Err(Error::ReadingConfig(_)) => Ok(Self::default()),
+            result => result,
+        }
+    }
+
+    fn remove_config<W: Workspace>(workspace: &W) -> Result<()> {
+        workspace.remove_config_object::<Self>()
+    }
+
+    fn config_filename() -> String {
+        "compilers".to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::workspace::workspace_dir::tests::make_temp_workspace;
+    use assert_fs::fixture::ChildPath;
+    use assert_fs::prelude::*;
+
+    #[test]
+    fn missing_config_should_use_defaults() {
+        let (temp_dir, _, workspace) = make_temp_workspace().unwrap();
+        workspace.initialize().unwrap();
+
+        let config = CompilerConfig::read_config(&workspace).unwrap();
+
+        assert_eq!(config, CompilerConfig::default());
+        assert_eq!(
+            config.compiler_for(&Language::Cpp).unwrap(),
+            Compiler::default_for(&Language::Cpp).unwrap()
+        );
+        temp_dir.close().unwrap();
+    }
+
+    #[test]
+    fn configured_compiler_should_override_default() {
+        let (temp_dir, mock_paths, workspace) = make_temp_workspace().unwrap();
+        workspace.initialize().unwrap();
+        ChildPath::new(mock_paths.config_path::<CompilerConfig>())
+            .write_str("cpp:\n  command: clang++\n  flags:\n  - -std=c++20\n")
+            .unwrap();
+
+        let config = CompilerConfig::read_config(&workspace).unwrap();
+
+        let expected = Compiler::new("clang++", &["-std=c++20"]);
+        assert_eq!(config.compiler_for(&Language::Cpp).unwrap(), expected);
+        assert_eq!(
+            config.compiler_for(&Language::CppWithFileSupport).unwrap(),
+            expected
+        );
+        assert_eq!(
+            config.compiler_for(&Language::Java).unwrap(),
+            Compiler::default_for(&Language::Java).unwrap()
+        );
+        temp_dir.close().unwrap();
+    }
+
+    #[test]
+    fn unsupported_language_should_fail() {
+        let result = CompilerConfig::default().compiler_for(&Language::Unsupported);
+
+        assert!(matches!(result, Err(Error::UnsupportedLanguage(_))));
+    }
+}
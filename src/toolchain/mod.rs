@@ -0,0 +1,7 @@
+pub use self::compiler::{check, compile, Compiler};
+pub use self::compiler_config::CompilerConfig;
+pub use self::program::{Program, RunOutput, RunStatus};
+
+mod compiler;
+mod compiler_config;
+mod program;
@@ -0,0 +1,133 @@
+use crate::error::Result;
+use std::ffi::{OsStr, OsString};
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::Path;
+use std::process::{self, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
+use tracing::debug;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RunStatus {
+    Exited,
+    Crashed(Option<i32>),
+    TimedOut,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RunOutput {
+    pub status: RunStatus,
+    pub stdout: String,
+    pub stderr: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct Program {
+    command: OsString,
+    args: Vec<OsString>,
+}
+
+impl Program {
+    pub fn new<S: AsRef<OsStr>>(command: S, args: &[&OsStr]) -> Self {
+        Self {
+            command: command.as_ref().to_os_string(),
+            args: args.iter().map(|x| x.to_os_string()).collect(),
+        }
+    }
+
+    /// Runs the program with `input` as stdin, killing it after `timeout`.
+    pub fn run(
+        &self,
+        args: &[String],
+        input: Option<&Path>,
+        timeout: Duration,
+    ) -> Result<RunOutput> {
+        let stdin = match input {
+            None => Stdio::null(),
+            Some(path) => File::open(path)?.into(),
+        };
+        // Captured into files instead of pipes, so a chatty program cannot block on a full pipe.
+        let mut stdout = tempfile::tempfile()?;
+        let mut stderr = tempfile::tempfile()?;
+
+        let mut cmd = process::Command::new(&self.command);
+        cmd.args(&self.args)
+            .args(args)
+            .stdin(stdin)
+            .stdout(stdout.try_clone()?)
+            .stderr(stderr.try_clone()?);
+        debug!("Running {:?}", cmd);
+
+        let mut child = cmd.spawn()?;
+        let start = Instant::now();
+
+        let status = loop {
+            if let Some(exit_status) = child.try_wait()? {
+                break match exit_status.success() {
+                    true => RunStatus::Exited,
+                    false => RunStatus::Crashed(exit_status.code()),
+                };
+            }
+
+            if start.elapsed() >= timeout {
+                child.kill()?;
+                child.wait()?;
+                break RunStatus::TimedOut;
+            }
+
+            thread::sleep(Duration::from_millis(5));
+        };
+
+        Ok(RunOutput {
+            status,
+            stdout: read_all(&mut stdout)?,
+            stderr: read_all(&mut stderr)?,
+        })
+    }
+}
+
+fn read_all(file: &mut File) -> Result<String> {
+    let mut buf = Vec::new();
+    file.seek(SeekFrom::Start(0))?;
+    file.read_to_end(&mut buf)?;
+    Ok(String::from_utf8_lossy(&buf).to_string())
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    fn bash(script: &str) -> Program {
+        Program::new("bash", &["-c".as_ref(), script.as_ref()])
+    }
+
+    #[test]
+    fn exit_code_should_be_reported() {
+        let output = bash("echo oops >&2; exit 3")
+            .run(&[], None, Duration::from_secs(5))
+            .unwrap();
+
+        assert_eq!(output.status, RunStatus::Crashed(Some(3)));
+        assert_eq!(output.stderr, "oops\n");
+    }
+
+    #[test]
+    fn long_running_program_should_time_out() {
+        let output = bash("sleep 5")
+            .run(&[], None, Duration::from_millis(100))
+            .unwrap();
+
+        assert_eq!(output.status, RunStatus::TimedOut);
+    }
+
+    #[test]
+    fn arguments_should_be_passed() {
+        let output = bash("echo $0")
+            .run(&["42".to_string()], None, Duration::from_secs(5))
+            .unwrap();
+
+        assert_eq!(output.status, RunStatus::Exited);
+        assert_eq!(output.stdout, "42\n");
+    }
+}
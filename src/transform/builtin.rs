@@ -0,0 +1,350 @@
+use crate::error::{Error, Result};
+use crate::model::Language;
+use crate::transform::{transform_each, SourceTransform};
+use crate::workspace;
+use crate::workspace::{HeaderFields, NormalizeConfig, SourceFile};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Submits the only selected file under a different name.
+pub struct Rename {
+    new_name: String,
+}
+
+impl Rename {
+    pub fn new(new_name: &str) -> Self {
+        Self {
+            new_name: new_name.to_string(),
+        }
+    }
+}
+
+impl SourceTransform for Rename {
+    fn name(&self) -> String {
+        "rename".to_string()
+    }
+
+    fn apply(&self, sources: &mut Vec<SourceFile>, step_dir: &Path) -> Result<()> {
+        let source = match sources.as_mut_slice() {
+            [source] => source,
+            _ => return Err(Error::RenamingMultipleFiles),
+        };
+
+        if source.name == Path::new(&self.new_name) {
+            return Ok(());
+        }
+
+        let renamed = step_dir.join(&self.new_name);
+        fs::copy(&source.path, &renamed)?;
+        source.path = renamed;
+        source.name = PathBuf::from(&self.new_name);
+
+        Ok(())
+    }
+}
+
+/// Fixes the encoding, BOM and line endings of every file and transliterates parts of it.
+pub struct Normalize {
+    config: NormalizeConfig,
+    language: Option<Language>,
+}
+
+impl Normalize {
+    pub fn new(config: NormalizeConfig, language: Option<Language>) -> Self {
+        Self { config, language }
+    }
+}
+
+impl SourceTransform for Normalize {
+    fn name(&self) -> String {
+        "normalize".to_string()
+    }
+
+    fn apply(&self, sources: &mut Vec<SourceFile>, step_dir: &Path) -> Result<()> {
+        for source in sources {
+            let language = file_language(source, self.language);
+            let output_dir = source.output_dir(step_dir)?;
+            source.path = workspace::normalize(&source.path, &language, &self.config, &output_dir)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Prepends a header rendered from `template` to the files without one.
+pub struct AddHeader {
+    template: String,
+    fields: HeaderFields,
+    language: Option<Language>,
+}
+
+impl AddHeader {
+    pub fn new(template: &str, fields: HeaderFields, language: Option<Language>) -> Self {
+        Self {
+            template: template.to_string(),
+            fields,
+            language,
+        }
+    }
+}
+
+impl SourceTransform for AddHeader {
+    fn name(&self) -> String {
+        "add-header".to_string()
+    }
+
+    fn apply(&self, sources: &mut Vec<SourceFile>, step_dir: &Path) -> Result<()> {
+        for source in sources {
+            let language = file_language(source, self.language);
+            let header = workspace::render_header(&self.template, &self.fields, &language)?;
+            let output_dir = source.output_dir(step_dir)?;
+            let path = workspace::add_header(&source.path, &header, &language, &output_dir)?;
+
+            if path != source.path {
+                println!(
+                    "Added header to {}: {}",
+                    source.name.to_string_lossy(),
+                    header
+                );
+                source.path = path;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Inlines quoted includes, looked up next to the original file.
+pub struct InlineIncludes;
+
+impl SourceTransform for InlineIncludes {
+    fn name(&self) -> String {
+        "inline-includes".to_string()
+    }
+
+    fn apply(&self, sources: &mut Vec<SourceFile>, step_dir: &Path) -> Result<()> {
+        println!("Submitting with local includes inlined");
+
+        for source in sources {
+            let include_dir = source.original.parent().unwrap_or_else(|| Path::new(""));
+            let output_dir = source.output_dir(step_dir)?;
+            source.path = workspace::inline_includes(&source.path, include_dir, &output_dir)?;
+        }
+
+        Ok(())
+    }
+}
+
+pub struct StripDebug {
+    language: Option<Language>,
+    strip_blocks: bool,
+}
+
+impl StripDebug {
+    pub fn new(language: Option<Language>, strip_blocks: bool) -> Self {
+        Self {
+            language,
+            strip_blocks,
+        }
+    }
+}
+
+impl SourceTransform for StripDebug {
+    fn name(&self) -> String {
+        "strip-debug".to_string()
+    }
+
+    fn apply(&self, sources: &mut Vec<SourceFile>, step_dir: &Path) -> Result<()> {
+        println!("Submitting with no debug code");
+
+        for source in sources {
+            let language = file_language(source, self.language);
+            let output_dir = source.output_dir(step_dir)?;
+            source.path =
+                workspace::strip_debug(&source.path, &language, self.strip_blocks, &output_dir)?;
+        }
+
+        Ok(())
+    }
+}
+
+pub struct NoMain {
+    language: Option<Language>,
+}
+
+impl NoMain {
+    pub fn new(language: Option<Language>) -> Self {
+        Self { language }
+    }
+}
+
+impl SourceTransform for NoMain {
+    fn name(&self) -> String {
+        "no-main".to_string()
+    }
+
+    fn apply(&self, sources: &mut Vec<SourceFile>, step_dir: &Path) -> Result<()> {
+        println!("Submitting with no main included");
+        let mut error = None;
+
+        // Only one of the files has to contain main, the rest is submitted unchanged.
+        for source in sources.iter_mut() {
+            let language = file_language(source, self.language);
+            let output_dir = source.output_dir(step_dir)?;
+
+            match workspace::remove_main(&source.path, &language, &output_dir) {
+                Ok(path) => {
+                    source.path = path;
+                    return Ok(());
+                }
+                Err(e @ (Error::MainNotFound | Error::UnsupportedLanguage(_))) => error = Some(e),
+                Err(e) => return Err(e),
+            }
+        }
+
+        Err(match sources.len() {
+            1 => error.unwrap_or(Error::MainNotFound),
+            _ => Error::MainNotFound,
+        })
+    }
+}
+
+pub struct NoPolish;
+
+impl SourceTransform for NoPolish {
+    fn name(&self) -> String {
+        "no-polish".to_string()
+    }
+
+    fn apply(&self, sources: &mut Vec<SourceFile>, step_dir: &Path) -> Result<()> {
+        println!("Submitting with no Polish diacritics");
+        transform_each(sources, step_dir, |file, dir| {
+            workspace::make_polishless_file(file, dir)
+        })
+    }
+}
+
+/// Files of a multi-file submit are not necessarily written in the task language, so the extension
+/// goes first. Task language is used for the files with unknown extensions.
+fn file_language(source: &SourceFile, task_language: Option<Language>) -> Language {
+    match (Language::from_path(&source.name), task_language) {
+        (Some(Language::Cpp), Some(Language::CppWithFileSupport)) => Language::CppWithFileSupport,
+        (Some(language), _) => language,
+        (None, Some(language)) => language,
+        (None, None) => Language::Unsupported,
+    }
+}
+
+/// Packs all sources into a single archive, which becomes the only source.
+pub struct Zip;
+
+impl SourceTransform for Zip {
+    fn name(&self) -> String {
+        "zip".to_string()
+    }
+
+    fn apply(&self, sources: &mut Vec<SourceFile>, step_dir: &Path) -> Result<()> {
+        let zipped = workspace::zip_files(sources, step_dir)?;
+        let name = PathBuf::from(zipped.file_name().ok_or(Error::InputFileDoesNotExist)?);
+        println!("Zipped as {}", name.to_string_lossy());
+
+        let mut archive = SourceFile::new(zipped, name);
+        archive.members = std::mem::take(sources);
+        sources.push(archive);
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert_fs::prelude::*;
+
+    #[test]
+    fn renamed_file_should_keep_content() {
+        let dir = assert_fs::TempDir::new().unwrap();
+        let input = dir.child("source.cpp");
+        input.write_str("// Hubert Jaremko\n").unwrap();
+        let step_dir = dir.child("step");
+        step_dir.create_dir_all().unwrap();
+        let mut sources = vec![SourceFile::new(
+            input.to_path_buf(),
+            PathBuf::from("source.cpp"),
+        )];
+
+        Rename::new("main.cpp")
+            .apply(&mut sources, step_dir.path())
+            .unwrap();
+
+        assert_eq!(sources[0].name, PathBuf::from("main.cpp"));
+        assert_eq!(sources[0].path, step_dir.join("main.cpp"));
+        step_dir.child("main.cpp").assert("// Hubert Jaremko\n");
+    }
+
+    #[test]
+    fn main_should_be_removed_from_the_file_containing_it() {
+        let dir = assert_fs::TempDir::new().unwrap();
+        let header = dir.child("matrix.h");
+        header.write_str("struct Matrix {};\n").unwrap();
+        let main = dir.child("main.cpp");
+        main.write_str("int f() {}\nint main() {}\n").unwrap();
+        let step_dir = dir.child("step");
+        step_dir.create_dir_all().unwrap();
+        let mut sources = vec![
+            SourceFile::new(header.to_path_buf(), PathBuf::from("matrix.h")),
+            SourceFile::new(main.to_path_buf(), PathBuf::from("main.cpp")),
+        ];
+
+        NoMain::new(Some(Language::Cpp))
+            .apply(&mut sources, step_dir.path())
+            .unwrap();
+
+        assert_eq!(sources[0].path, header.path());
+        step_dir.child("main.cpp").assert("int f() {}\n\n");
+    }
+
+    #[test]
+    fn missing_main_should_be_reported() {
+        let dir = assert_fs::TempDir::new().unwrap();
+        let header = dir.child("matrix.h");
+        header.write_str("struct Matrix {};\n").unwrap();
+        let mut sources = vec![SourceFile::new(
+            header.to_path_buf(),
+            PathBuf::from("matrix.h"),
+        )];
+
+        let result = NoMain::new(Some(Language::Cpp)).apply(&mut sources, dir.path());
+
+        assert!(matches!(result, Err(Error::MainNotFound)), "{:?}", result);
+    }
+
+    #[test]
+    fn header_should_be_added_only_where_missing() {
+        let dir = assert_fs::TempDir::new().unwrap();
+        let with_header = dir.child("matrix.h");
+        with_header.write_str("// Jan Kowalski\n").unwrap();
+        let without_header = dir.child("main.cpp");
+        without_header.write_str("int main() {}\n").unwrap();
+        let step_dir = dir.child("step");
+        step_dir.create_dir_all().unwrap();
+        let mut sources = vec![
+            SourceFile::new(with_header.to_path_buf(), PathBuf::from("matrix.h")),
+            SourceFile::new(without_header.to_path_buf(), PathBuf::from("main.cpp")),
+        ];
+        let fields = HeaderFields {
+            name: "Hubert Jaremko".to_string(),
+            login: "hjaremko".to_string(),
+            task: "[A] Zera funkcji".to_string(),
+        };
+
+        AddHeader::new("{name} {login}", fields, Some(Language::Cpp))
+            .apply(&mut sources, step_dir.path())
+            .unwrap();
+
+        assert_eq!(sources[0].path, with_header.path());
+        step_dir
+            .child("main.cpp")
+            .assert("// Hubert Jaremko hjaremko\nint main() {}\n");
+    }
+}
@@ -0,0 +1,113 @@
+use crate::error::{Error, Result};
+use crate::transform::{transform_each, SourceTransform};
+use crate::workspace::SourceFile;
+use std::fs::File;
+use std::path::{Path, PathBuf};
+use std::process;
+use tracing::debug;
+
+/// Transform provided by the user, a shell command reading the file from stdin and writing
+/// the result to stdout.
+pub struct External {
+    command: String,
+}
+
+impl External {
+    pub fn new(command: &str) -> Self {
+        Self {
+            command: command.to_string(),
+        }
+    }
+
+    fn run(&self, input: &Path, output_dir: &Path) -> Result<PathBuf> {
+        let output = output_dir.join(input.file_name().ok_or(Error::InputFileDoesNotExist)?);
+
+        let mut cmd = shell_command(&self.command);
+        cmd.stdin(File::open(input)?)
+            .stdout(File::create(&output)?)
+            .stderr(process::Stdio::piped());
+        debug!("Running {:?}", cmd);
+
+        let result = cmd.output()?;
+
+        if !result.status.success() {
+            return Err(Error::TransformFailed(
+                self.command.clone(),
+                String::from_utf8_lossy(&result.stderr).to_string(),
+            ));
+        }
+
+        Ok(output)
+    }
+}
+
+impl SourceTransform for External {
+    fn name(&self) -> String {
+        "external".to_string()
+    }
+
+    fn apply(&self, sources: &mut Vec<SourceFile>, step_dir: &Path) -> Result<()> {
+        println!("Transforming with {}", self.command);
+        transform_each(sources, step_dir, |file, dir| self.run(file, dir))
+    }
+}
+
+#[cfg(unix)]
+fn shell_command(command: &str) -> process::Command {
+    let mut cmd = process::Command::new("sh");
+    cmd.arg("-c").arg(command);
+    cmd
+}
+
+#[cfg(windows)]
+fn shell_command(command: &str) -> process::Command {
+    let mut cmd = process::Command::new("cmd");
+    cmd.arg("/C").arg(command);
+    cmd
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use assert_fs::prelude::*;
+
+    fn make_sources(dir: &assert_fs::TempDir) -> Vec<SourceFile> {
+        let input = dir.child("source.cpp");
+        input.write_str("int main() {}\n").unwrap();
+        vec![SourceFile::new(
+            input.to_path_buf(),
+            PathBuf::from("source.cpp"),
+        )]
+    }
+
+    #[test]
+    fn output_should_replace_file() {
+        let dir = assert_fs::TempDir::new().unwrap();
+        let step_dir = dir.child("step");
+        step_dir.create_dir_all().unwrap();
+        let mut sources = make_sources(&dir);
+
+        External::new("tr a-z A-Z")
+            .apply(&mut sources, step_dir.path())
+            .unwrap();
+
+        assert_eq!(sources[0].path, step_dir.join("source.cpp"));
+        step_dir.child("source.cpp").assert("INT MAIN() {}\n");
+    }
+
+    #[test]
+    fn failing_command_should_be_reported() {
+        let dir = assert_fs::TempDir::new().unwrap();
+        let step_dir = dir.child("step");
+        step_dir.create_dir_all().unwrap();
+        let mut sources = make_sources(&dir);
+
+        let result = External::new("echo broken >&2; exit 1").apply(&mut sources, step_dir.path());
+
+        assert!(
+            matches!(&result, Err(Error::TransformFailed(_, stderr)) if stderr == "broken\n"),
+            "{:?}",
+            result
+        );
+    }
+}
@@ -0,0 +1,188 @@
+use crate::error::{Error, Result};
+use crate::workspace::{HeaderFields, SourceFile, StagingDir, SubmitConfig};
+use std::path::{Path, PathBuf};
+use tracing::info;
+
+pub use self::builtin::{
+    AddHeader, InlineIncludes, NoMain, NoPolish, Normalize, Rename, StripDebug, Zip,
+};
+pub use self::external::External;
+pub use self::transform_config::{BuiltinTransform, TransformConfig};
+
+mod builtin;
+mod external;
+mod transform_config;
+
+/// A single step of preparing the sources for submitting.
+pub trait SourceTransform {
+    /// Short name, used for the staging directory of the step.
+    fn name(&self) -> String;
+
+    /// Transforms `sources`, writing all new files into `step_dir`.
+    fn apply(&self, sources: &mut Vec<SourceFile>, step_dir: &Path) -> Result<()>;
+}
+
+pub type Pipeline = Vec<Box<dyn SourceTransform>>;
+
+/// Builds the pipeline declared in `submit_config`, renaming goes first and zipping last.
+pub fn make_pipeline(
+    submit_config: &SubmitConfig,
+    source_count: usize,
+    header_fields: &HeaderFields,
+) -> Result<Pipeline> {
+    let mut pipeline: Pipeline = Vec::new();
+
+    if let Some(new_name) = &submit_config.rename_as {
+        if source_count > 1 {
+            return Err(Error::RenamingMultipleFiles);
+        }
+
+        pipeline.push(Box::new(Rename::new(new_name)));
+    }
+
+    let mut transforms = submit_config.transforms();
+
+    if source_count > 1 && !transforms.contains(&BuiltinTransform::Zip.into()) {
+        info!("Multiple files selected, zipping enabled");
+        transforms.push(BuiltinTransform::Zip.into());
+    }
+
+    if let Some(position) = transforms
+        .iter()
+        .position(|x| *x == BuiltinTransform::Zip.into())
+    {
+        if position + 1 != transforms.len() {
+            return Err(Error::InvalidTransforms(
+                "zip has to be the last transform".to_string(),
+            ));
+        }
+    }
+
+    pipeline.extend(
+        transforms
+            .iter()
+            .map(|x| x.make_transform(submit_config, header_fields)),
+    );
+    Ok(pipeline)
+}
+
+pub fn run_pipeline(
+    pipeline: &Pipeline,
+    sources: &mut Vec<SourceFile>,
+    staging: &StagingDir,
+) -> Result<()> {
+    for transform in pipeline {
+        let step_dir = staging.step(&transform.name())?;
+        transform.apply(sources, &step_dir)?;
+        info!("Sources after {}: {:?}", transform.name(), sources);
+    }
+
+    Ok(())
+}
+
+/// Runs `transform` on every file separately, keeping their names.
+pub fn transform_each<F>(sources: &mut [SourceFile], step_dir: &Path, transform: F) -> Result<()>
+where
+    F: Fn(&Path, &Path) -> Result<PathBuf>,
+{
+    for source in sources {
+        let output_dir = source.output_dir(step_dir)?;
+        source.path = transform(&source.path, &output_dir)?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::Language;
+    use assert_fs::prelude::*;
+
+    fn names(pipeline: &Pipeline) -> Vec<String> {
+        pipeline.iter().map(|x| x.name()).collect()
+    }
+
+    #[test]
+    fn pipeline_should_follow_declared_order() {
+        let mut submit_config =
+            SubmitConfig::new("1", Path::new("a.cpp"), false, Language::Cpp, None);
+        submit_config.transforms = vec![
+            BuiltinTransform::NoPolish.into(),
+            TransformConfig::External {
+                command: "clang-format".to_string(),
+            },
+            BuiltinTransform::NoMain.into(),
+        ];
+
+        let pipeline = make_pipeline(&submit_config, 1, &HeaderFields::default()).unwrap();
+
+        assert_eq!(names(&pipeline), vec!["no-polish", "external", "no-main"]);
+    }
+
+    #[test]
+    fn multiple_files_should_be_zipped() {
+        let submit_config = SubmitConfig::new(
+            "1",
+            Path::new("src"),
+            false,
+            Language::Cpp,
+            Some("renamed.cpp".to_string()),
+        );
+
+        assert!(matches!(
+            make_pipeline(&submit_config, 2, &HeaderFields::default()),
+            Err(Error::RenamingMultipleFiles)
+        ));
+
+        let submit_config = SubmitConfig::new("1", Path::new("src"), false, Language::Cpp, None);
+        let pipeline = make_pipeline(&submit_config, 2, &HeaderFields::default()).unwrap();
+
+        assert_eq!(names(&pipeline), vec!["zip"]);
+    }
+
+    #[test]
+    fn zip_should_be_last() {
+        let mut submit_config =
+            SubmitConfig::new("1", Path::new("a.cpp"), false, Language::Cpp, None);
+        submit_config.transforms = vec![
+            BuiltinTransform::Zip.into(),
+            BuiltinTransform::NoMain.into(),
+        ];
+
+        let result = make_pipeline(&submit_config, 1, &HeaderFields::default());
+
+        assert!(matches!(result, Err(Error::InvalidTransforms(_))));
+    }
+
+    #[test]
+    fn every_step_should_write_into_its_own_directory() {
+        let dir = assert_fs::TempDir::new().unwrap();
+        let input = dir.child("source.cpp");
+        input
+            .write_str("// Zażółć\nint f() {}\nint main() {}\n")
+            .unwrap();
+        let mut sources = vec![SourceFile::new(
+            input.to_path_buf(),
+            PathBuf::from("source.cpp"),
+        )];
+        let pipeline: Pipeline = vec![
+            Box::new(NoPolish),
+            Box::new(NoMain::new(Some(Language::Cpp))),
+            Box::new(Zip),
+        ];
+        let staging = StagingDir::new().unwrap();
+
+        run_pipeline(&pipeline, &mut sources, &staging).unwrap();
+
+        assert_eq!(sources.len(), 1);
+        assert_eq!(sources[0].name, PathBuf::from("source.zip"));
+        let member = &sources[0].members[0];
+        assert!(member.path.starts_with(staging.path().join("02-no-main")));
+        assert_eq!(
+            std::fs::read_to_string(&member.path).unwrap(),
+            "// Zazolc\nint f() {}\n\n"
+        );
+        input.assert("// Zażółć\nint f() {}\nint main() {}\n");
+    }
+}
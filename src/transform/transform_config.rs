@@ -0,0 +1,88 @@
+use crate::transform::{
+    AddHeader, External, InlineIncludes, NoMain, NoPolish, Normalize, SourceTransform, StripDebug,
+    Zip,
+};
+use crate::workspace::{HeaderFields, SubmitConfig};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum BuiltinTransform {
+    Normalize,
+    AddHeader,
+    InlineIncludes,
+    StripDebug,
+    NoMain,
+    NoPolish,
+    Zip,
+}
+
+/// Single entry of the `transforms` list in the submit config.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum TransformConfig {
+    Builtin(BuiltinTransform),
+    External { command: String },
+}
+
+impl TransformConfig {
+    pub fn make_transform(
+        &self,
+        submit_config: &SubmitConfig,
+        header_fields: &HeaderFields,
+    ) -> Box<dyn SourceTransform> {
+        match self {
+            TransformConfig::Builtin(BuiltinTransform::Normalize) => Box::new(Normalize::new(
+                submit_config.normalize.clone(),
+                submit_config.language,
+            )),
+            TransformConfig::Builtin(BuiltinTransform::AddHeader) => Box::new(AddHeader::new(
+                submit_config.header.template(),
+                header_fields.clone(),
+                submit_config.language,
+            )),
+            TransformConfig::Builtin(BuiltinTransform::InlineIncludes) => Box::new(InlineIncludes),
+            TransformConfig::Builtin(BuiltinTransform::StripDebug) => Box::new(StripDebug::new(
+                submit_config.language,
+                submit_config.strip_debug_blocks,
+            )),
+            TransformConfig::Builtin(BuiltinTransform::NoMain) => {
+                Box::new(NoMain::new(submit_config.language))
+            }
+            TransformConfig::Builtin(BuiltinTransform::NoPolish) => Box::new(NoPolish),
+            TransformConfig::Builtin(BuiltinTransform::Zip) => Box::new(Zip),
+            TransformConfig::External { command } => Box::new(External::new(command)),
+        }
+    }
+}
+
+impl From<BuiltinTransform> for TransformConfig {
+    fn from(transform: BuiltinTransform) -> Self {
+        TransformConfig::Builtin(transform)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn transforms_should_be_read_from_yaml() {
+        let yaml = "- strip-debug\n- no-main\n- command: clang-format\n- zip\n";
+
+        let transforms: Vec<TransformConfig> = serde_yaml::from_str(yaml).unwrap();
+
+        assert_eq!(
+            transforms,
+            vec![
+                BuiltinTransform::StripDebug.into(),
+                BuiltinTransform::NoMain.into(),
+                TransformConfig::External {
+                    command: "clang-format".to_string()
+                },
+                BuiltinTransform::Zip.into(),
+            ]
+        );
+        assert_eq!(serde_yaml::to_string(&transforms).unwrap(), yaml);
+    }
+}
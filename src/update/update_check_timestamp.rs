@@ -62,6 +62,10 @@ impl ConfigObject for UpdateCheckTimestamp {
     fn config_filename() -> String {
         TIMESTAMP_FILENAME.to_string()
     }
+
+    fn is_global() -> bool {
+        true
+    }
 }
 
 #[cfg(test)]
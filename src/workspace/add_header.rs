@@ -0,0 +1,192 @@
+use crate::error::*;
+use crate::model::Language;
+use crate::workspace::header_check::is_header_present;
+use std::fs;
+use std::fs::File;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use tracing::{debug, info};
+
+/// Values available in the header template.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct HeaderFields {
+    pub name: String,
+    pub login: String,
+    pub task: String,
+}
+
+/// Fills `{name}`, `{login}` and `{task}` in `template` and turns it into a comment,
+/// unless it is a block comment already. Each line of a multi-line template is commented.
+pub fn render_header(template: &str, fields: &HeaderFields, language: &Language) -> Result<String> {
+    let header = template
+        .replace("{name}", &fields.name)
+        .replace("{login}", &fields.login)
+        .replace("{task}", &fields.task);
+
+    let is_block_comment = language
+        .block_comment_style()
+        .is_some_and(|(begin, _)| header.starts_with(begin));
+
+    if is_block_comment {
+        return Ok(header);
+    }
+
+    let style = language
+        .comment_styles()
+        .and_then(|styles| styles.first().map(|x| x.to_string()))
+        .ok_or_else(|| Error::UnsupportedLanguage(language.to_string()))?;
+
+    let lines = header
+        .lines()
+        .map(|line| match line {
+            _ if language.is_comment(line) => line.to_string(),
+            "" => style.clone(),
+            _ => format!("{} {}", style, line),
+        })
+        .collect::<Vec<_>>();
+
+    Ok(lines.join("\n"))
+}
+
+/// Prepends `header` to `input_file`, if it has none yet.
+pub fn add_header<P, Q>(
+    input_file: P,
+    header: &str,
+    language: &Language,
+    output_dir: Q,
+) -> Result<PathBuf>
+where
+    P: AsRef<Path>,
+    Q: AsRef<Path>,
+{
+    let input_file: &Path = input_file.as_ref();
+    info!("Adding header to {:?}", input_file);
+
+    if is_header_present(input_file, language)? {
+        info!("Header already present");
+        return Ok(input_file.to_path_buf());
+    }
+
+    let content = fs::read_to_string(input_file)?;
+    let content = format!("{}\n{}", header, content);
+
+    let filepath = output_dir
+        .as_ref()
+        .join(input_file.file_name().ok_or(Error::InputFileDoesNotExist)?);
+    let mut file = File::create(filepath.clone())?;
+    file.write_all(content.as_ref())?;
+
+    debug!("New input file path: {:?}", filepath);
+    debug!("New input file content:\n{}", content);
+
+    Ok(filepath)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert_fs::prelude::*;
+    use assert_fs::TempDir;
+
+    fn make_fields() -> HeaderFields {
+        HeaderFields {
+            name: "Hubert Jaremko".to_string(),
+            login: "hjaremko".to_string(),
+            task: "[A] Zera funkcji".to_string(),
+        }
+    }
+
+    #[test]
+    fn template_should_be_filled() {
+        let header =
+            render_header("// {name} {login} {task}", &make_fields(), &Language::Cpp).unwrap();
+
+        assert_eq!(header, "// Hubert Jaremko hjaremko [A] Zera funkcji");
+    }
+
+    #[test]
+    fn template_without_comment_should_follow_language() {
+        let fields = make_fields();
+
+        assert_eq!(
+            render_header("{name}", &fields, &Language::Bash).unwrap(),
+            "# Hubert Jaremko"
+        );
+        assert_eq!(
+            render_header("{name}", &fields, &Language::Ada).unwrap(),
+            "-- Hubert Jaremko"
+        );
+        assert_eq!(
+            render_header("/* {login} */", &fields, &Language::Java).unwrap(),
+            "/* hjaremko */"
+        );
+    }
+
+    #[test]
+    fn every_line_of_template_should_be_commented() {
+        let header = render_header(
+            "// {name}\n{login}\n\n{task}",
+            &make_fields(),
+            &Language::Cpp,
+        )
+        .unwrap();
+
+        assert_eq!(
+            header,
+            "// Hubert Jaremko\n// hjaremko\n//\n// [A] Zera funkcji"
+        );
+    }
+
+    #[test]
+    fn unsupported_language_should_fail() {
+        let result = render_header("{name}", &make_fields(), &Language::Unsupported);
+
+        assert!(
+            matches!(result, Err(Error::UnsupportedLanguage(_))),
+            "{:?}",
+            result
+        );
+    }
+
+    #[test]
+    fn header_should_be_prepended_when_missing() {
+        let dir = TempDir::new().unwrap();
+        let output_dir = TempDir::new().unwrap();
+        let input = dir.child("main.cpp");
+        input.write_str("int main() {}\n").unwrap();
+
+        let actual = add_header(
+            input.path(),
+            "// Hubert Jaremko",
+            &Language::Cpp,
+            output_dir.path(),
+        )
+        .unwrap();
+
+        assert_eq!(actual, output_dir.join("main.cpp"));
+        assert_eq!(
+            fs::read_to_string(actual).unwrap(),
+            "// Hubert Jaremko\nint main() {}\n"
+        );
+    }
+
+    #[test]
+    fn existing_header_should_be_kept() {
+        let dir = TempDir::new().unwrap();
+        let output_dir = TempDir::new().unwrap();
+        let input = dir.child("main.cpp");
+        input
+            .write_str("/* Jan Kowalski */\nint main() {}\n")
+            .unwrap();
+
+        let actual = add_header(
+            input.path(),
+            "// Hubert Jaremko",
+            &Language::Cpp,
+            output_dir.path(),
+        )
+        .unwrap();
+
+        assert_eq!(actual, input.path());
+    }
+}
@@ -139,7 +139,7 @@ mod tests {
             .returning(|| Ok(()));
         workspace_mock
             .expect_get_paths()
-            .returning(move || WorkspacePaths::_with_root(&temp_dir));
+            .returning(move || WorkspacePaths::with_root(&temp_dir));
         let mut spawner_mock = MockEditorSpawner::new();
         spawner_mock
             .expect_spawn_and_wait()
@@ -168,7 +168,7 @@ mod tests {
             .returning(|| Ok(()));
         workspace_mock
             .expect_get_paths()
-            .returning(move || WorkspacePaths::_with_root(&temp_dir));
+            .returning(move || WorkspacePaths::with_root(&temp_dir));
         let mut spawner_mock = MockEditorSpawner::new();
         spawner_mock.expect_spawn_and_wait().returning(move |_| {
             {
@@ -202,7 +202,7 @@ mod tests {
             .returning(|| Ok(()));
         workspace_mock
             .expect_get_paths()
-            .returning(move || WorkspacePaths::_with_root(&temp_dir));
+            .returning(move || WorkspacePaths::with_root(&temp_dir));
         let mut spawner_mock = MockEditorSpawner::new();
         spawner_mock.expect_spawn_and_wait().returning(move |_| {
             config_mock.write_all(b"Test!").unwrap();
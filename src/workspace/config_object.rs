@@ -9,4 +9,9 @@ pub trait ConfigObject: Serialize + DeserializeOwned + Debug + Sized {
     fn read_config<W: Workspace>(workspace: &W) -> Result<Self>;
     fn remove_config<W: Workspace>(workspace: &W) -> Result<()>;
     fn config_filename() -> String;
+
+    /// Stored in the user config directory and shared by all workspaces.
+    fn is_global() -> bool {
+        false
+    }
 }
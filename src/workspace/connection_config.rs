@@ -1,22 +1,283 @@
 use crate::api;
 use crate::error::Error;
 use crate::error::Result;
-use crate::workspace::{ConfigObject, Workspace};
+use crate::log;
+use crate::workspace::secret_store::{restrict_permissions, unlock};
+use crate::workspace::{ConfigObject, CredentialProvider, SecretKey, UserConfig, Workspace};
 use serde::{Deserialize, Serialize};
-use tracing::error;
+use std::fmt;
+use std::sync::Mutex;
+use tracing::{debug, error, info};
 
-#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default)]
+/// Overrides given for this run, see [`ConnectionOverrides::activate`].
+static OVERRIDES: Mutex<Option<ConnectionOverrides>> = Mutex::new(None);
+
+/// Part of [`ConnectionConfig`] kept encrypted.
+#[derive(Serialize, Deserialize)]
+struct Secrets {
+    password: String,
+    cookie: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Default)]
 pub struct ConnectionConfig {
     pub host: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
     pub login: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
     pub password: String,
+    /// Command printing the password, used instead of storing it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub password_command: Option<String>,
     pub permutation: String,
     pub cookie: String,
+    /// Password and cookie encrypted with the user passphrase, see [`Encryption`](super::Encryption).
+    /// When set, they are encrypted on every save.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub secrets: Option<String>,
+    /// User profile providing the credentials, see [`UserConfig`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub profile: Option<String>,
+}
+
+/// Connection settings from `BACA_*` variables or options, taking precedence over the saved ones.
+#[derive(Clone, Default, PartialEq, Eq)]
+pub struct ConnectionOverrides {
+    pub host: Option<String>,
+    pub login: Option<String>,
+    pub password: Option<String>,
+    pub cookie: Option<String>,
+}
+
+impl fmt::Debug for ConnectionOverrides {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ConnectionOverrides")
+            .field("host", &self.host)
+            .field("login", &self.login)
+            .field("password", &self.password.as_deref().map(log::mask))
+            .field("cookie", &self.cookie.as_deref().map(log::mask))
+            .finish()
+    }
+}
+
+impl ConnectionOverrides {
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Applies the overrides to every connection config read for the rest of the run.
+    pub fn activate(self) {
+        debug!("Connection overrides: {:?}", self);
+        *OVERRIDES.lock().unwrap() = Some(self).filter(|x| !x.is_empty());
+    }
+
+    fn active() -> Option<Self> {
+        OVERRIDES.lock().unwrap().clone()
+    }
+
+    fn apply(&self, mut config: ConnectionConfig) -> ConnectionConfig {
+        if let Some(host) = &self.host {
+            config.host = host.clone();
+        }
+
+        if let Some(login) = &self.login {
+            config.login = login.clone();
+        }
+
+        if let Some(password) = &self.password {
+            config.password = password.clone();
+            config.password_command = None;
+        }
+
+        if let Some(cookie) = &self.cookie {
+            config.cookie = cookie.clone();
+        }
+
+        config
+    }
+
+    /// Puts back the saved values of the overridden fields, so the overrides are never saved.
+    /// Fields changed since, like a new cookie, are kept.
+    fn restore(&self, mut config: ConnectionConfig, saved: &ConnectionConfig) -> ConnectionConfig {
+        if self.host.as_ref() == Some(&config.host) {
+            config.host = saved.host.clone();
+        }
+
+        if self.login.as_ref() == Some(&config.login) {
+            config.login = saved.login.clone();
+        }
+
+        if self.password.as_ref() == Some(&config.password) {
+            config.password = saved.password.clone();
+            config.password_command = saved.password_command.clone();
+        }
+
+        if self.cookie.as_ref() == Some(&config.cookie) {
+            config.cookie = saved.cookie.clone();
+        }
+
+        config
+    }
+}
+
+/// Masks the password and cookie, so the config can be logged.
+impl fmt::Debug for ConnectionConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ConnectionConfig")
+            .field("host", &self.host)
+            .field("login", &self.login)
+            .field("password", &log::mask(&self.password))
+            .field("password_command", &self.password_command)
+            .field("permutation", &self.permutation)
+            .field("cookie", &log::mask(&self.cookie))
+            .field("secrets", &self.secrets.as_deref().map(log::mask))
+            .field("profile", &self.profile)
+            .finish()
+    }
 }
 
 impl ConnectionConfig {
-    pub fn credentials(&self) -> (String, String) {
-        (self.login.clone(), self.password.clone())
+    /// Fills the missing credentials from the referenced profile.
+    fn apply_profile<W: Workspace>(
+        mut self,
+        workspace: &W,
+        user_config: &UserConfig,
+    ) -> Result<Self> {
+        if let Some(name) = &self.profile {
+            let profile = user_config.profile(name)?;
+
+            if self.login.is_empty() {
+                self.login = profile.login.clone();
+            }
+
+            if self.password.is_empty() && self.password_command.is_none() {
+                self.password = user_config.profile_password(workspace, profile)?;
+                self.password_command = profile.password_command.clone();
+            }
+        }
+
+        Ok(self)
+    }
+
+    /// Leaves out the credentials which come from the referenced profile.
+    fn strip_profile<W: Workspace>(
+        mut self,
+        workspace: &W,
+        user_config: &UserConfig,
+    ) -> Result<Self> {
+        if let Some(name) = &self.profile {
+            let profile = user_config.profile(name)?;
+
+            if self.login == profile.login {
+                self.login.clear();
+            }
+
+            if !self.password.is_empty()
+                && self.password == user_config.profile_password(workspace, profile)?
+            {
+                self.password.clear();
+            }
+
+            if self.password_command == profile.password_command {
+                self.password_command = None;
+            }
+        }
+
+        Ok(self)
+    }
+
+    fn register_secrets(&self) {
+        log::register_secret(&self.password);
+        log::register_secret(&self.cookie);
+    }
+
+    fn encrypt_secrets(mut self, key: &SecretKey) -> Result<Self> {
+        let secrets = Secrets {
+            password: std::mem::take(&mut self.password),
+            cookie: std::mem::take(&mut self.cookie),
+        };
+        self.secrets = Some(key.encrypt(&serde_json::to_vec(&secrets)?)?);
+
+        Ok(self)
+    }
+
+    fn decrypt_secrets(mut self, key: &SecretKey) -> Result<Self> {
+        let encrypted = self.secrets.as_deref().unwrap_or_default();
+        let secrets: Secrets = serde_json::from_slice(&key.decrypt(encrypted)?)
+            .map_err(|_| Error::SecretsCorrupted)?;
+        self.password = secrets.password;
+        self.cookie = secrets.cookie;
+
+        Ok(self)
+    }
+
+    /// The config as saved, without the overrides.
+    fn read_saved<W: Workspace>(workspace: &W) -> Result<Self> {
+        let config = workspace.read_config_object::<Self>().map_err(|e| {
+            error!("{:?}", e);
+            match e {
+                Error::WorkspaceNotInitialized => e,
+                _ => Error::WorkspaceCorrupted,
+            }
+        })?;
+
+        let user_config = match (&config.profile, &config.secrets) {
+            (None, None) => return Ok(config),
+            _ => UserConfig::read_config(workspace)?,
+        };
+
+        let config = match &config.secrets {
+            Some(_) => {
+                let encryption = user_config
+                    .encryption
+                    .as_ref()
+                    .ok_or(Error::EncryptionNotSetUp)?;
+                config.decrypt_secrets(&unlock(workspace, encryption)?)?
+            }
+            None => config,
+        };
+
+        match config.profile {
+            Some(_) => config.apply_profile(workspace, &user_config),
+            None => Ok(config),
+        }
+    }
+
+    /// Encrypts the secrets saved before the encryption was set up. Returns whether anything changed.
+    pub fn encrypt_plaintext<W: Workspace>(workspace: &W) -> Result<bool> {
+        if UserConfig::read_config(workspace)?.encryption.is_none() {
+            return Ok(false);
+        }
+
+        let mut config = match workspace.read_config_object::<Self>() {
+            Ok(config) => config,
+            Err(_) => return Ok(false),
+        };
+
+        if config.secrets.is_some() || (config.password.is_empty() && config.cookie.is_empty()) {
+            return Ok(false);
+        }
+
+        info!("Encrypting plaintext secrets.");
+        config.secrets = Some(String::new());
+        config.save_config(workspace)?;
+        Ok(true)
+    }
+
+    /// A password command takes precedence over the stored password. Without either,
+    /// the password is asked for.
+    pub fn credential_provider(&self) -> CredentialProvider {
+        match (&self.password_command, self.password.is_empty()) {
+            (Some(command), _) => CredentialProvider::Command(command.clone()),
+            (None, false) => CredentialProvider::Plain(self.password.clone()),
+            (None, true) => CredentialProvider::Prompt,
+        }
+    }
+
+    pub fn credentials(&self) -> Result<(String, String)> {
+        let password = self.credential_provider().password()?;
+        log::register_secret(&password);
+        Ok((self.login.clone(), password))
     }
 
     pub fn make_url(&self) -> String {
@@ -30,11 +291,14 @@ impl ConnectionConfig {
     pub fn make_payload(&self, req_type: &api::RequestType) -> String {
         use dyn_fmt::AsStrFormatExt;
 
-        req_type.payload_template().format(&[
-            self.make_module_base(),
-            self.login.clone(),
-            self.password.clone(),
-        ])
+        let (login, password) = match req_type {
+            api::RequestType::Login(login, password) => (login.clone(), password.clone()),
+            _ => Default::default(),
+        };
+
+        req_type
+            .payload_template()
+            .format(&[self.make_module_base(), login, password])
     }
 
     pub fn make_cookie(&self) -> String {
@@ -44,23 +308,50 @@ impl ConnectionConfig {
 
 impl ConfigObject for ConnectionConfig {
     fn save_config<W: Workspace>(&self, workspace: &W) -> Result<()> {
-        workspace.save_config_object(self).map_err(|e| {
+        let config = match ConnectionOverrides::active() {
+            Some(overrides) => match Self::read_saved(workspace) {
+                Ok(saved) => overrides.restore(self.clone(), &saved),
+                Err(_) => self.clone(),
+            },
+            None => self.clone(),
+        };
+
+        let config = match config.profile {
+            Some(_) => config.strip_profile(workspace, &UserConfig::read_config(workspace)?)?,
+            None => config,
+        };
+
+        let config = match config.secrets {
+            Some(_) => {
+                let user_config = UserConfig::read_config(workspace)?;
+                let encryption = user_config.encryption.ok_or(Error::EncryptionNotSetUp)?;
+                config.encrypt_secrets(&unlock(workspace, &encryption)?)?
+            }
+            None => config,
+        };
+
+        workspace.save_config_object(&config).map_err(|e| {
             error!("{:?}", e);
             match e {
                 Error::WorkspaceNotInitialized => e,
                 _ => Error::WorkspaceCorrupted,
             }
-        })
+        })?;
+
+        match config.secrets {
+            Some(_) => restrict_permissions(&workspace.get_paths().config_path::<Self>()),
+            None => Ok(()),
+        }
     }
 
     fn read_config<W: Workspace>(workspace: &W) -> Result<Self> {
-        workspace.read_config_object::<Self>().map_err(|e| {
-            error!("{:?}", e);
-            match e {
-                Error::WorkspaceNotInitialized => e,
-                _ => Error::WorkspaceCorrupted,
-            }
-        })
+        let config = match ConnectionOverrides::active() {
+            Some(overrides) => overrides.apply(Self::read_saved(workspace)?),
+            None => Self::read_saved(workspace)?,
+        };
+
+        config.register_secrets();
+        Ok(config)
     }
 
     fn remove_config<W: Workspace>(workspace: &W) -> Result<()> {
@@ -81,9 +372,16 @@ impl ConfigObject for ConnectionConfig {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::workspace::secret_store::remember;
     use crate::workspace::workspace_dir::tests::{make_baca, make_temp_workspace};
-    use crate::workspace::{ConfigObject, ConnectionConfig};
+    use crate::workspace::{
+        ConfigObject, ConnectionConfig, Encryption, Profile, WorkspaceDir, WorkspacePaths,
+    };
+    use assert_fs::prelude::*;
+    use assert_fs::TempDir;
     use predicates::prelude::*;
+    use std::fs;
+    use std::path::Path;
 
     #[test]
     fn save_read_success() {
@@ -136,5 +434,247 @@ mod tests {
 
         temp_dir.close().unwrap();
     }
+
+    #[test]
+    fn credentials_should_come_from_profile() {
+        let (temp_dir, mock_paths, workspace) = make_temp_workspace().unwrap();
+        workspace.initialize().unwrap();
+        let mut user_config = UserConfig::default();
+        user_config.profiles.insert(
+            "uj".to_string(),
+            Profile {
+                login: "test_login".to_string(),
+                password: "test_pass".to_string(),
+                ..Default::default()
+            },
+        );
+        user_config.save_config(&workspace).unwrap();
+        let mut baca = make_baca();
+        baca.profile = Some("uj".to_string());
+
+        baca.save_config(&workspace).unwrap();
+
+        let saved = fs::read_to_string(mock_paths.config_path::<ConnectionConfig>()).unwrap();
+        assert!(!saved.contains("test_login"), "{}", saved);
+        assert!(!saved.contains("test_pass"), "{}", saved);
+        assert_eq!(ConnectionConfig::read_config(&workspace).unwrap(), baca);
+        temp_dir.close().unwrap();
+    }
+
+    #[test]
+    fn workspace_credentials_should_override_profile() {
+        let (temp_dir, _, workspace) = make_temp_workspace().unwrap();
+        workspace.initialize().unwrap();
+        let mut user_config = UserConfig::default();
+        user_config.profiles.insert(
+            "uj".to_string(),
+            Profile {
+                login: "profile_login".to_string(),
+                password: "profile_password".to_string(),
+                ..Default::default()
+            },
+        );
+        user_config.save_config(&workspace).unwrap();
+        let mut baca = make_baca();
+        baca.profile = Some("uj".to_string());
+
+        baca.save_config(&workspace).unwrap();
+
+        assert_eq!(ConnectionConfig::read_config(&workspace).unwrap(), baca);
+        temp_dir.close().unwrap();
+    }
+
+    #[test]
+    fn missing_profile_should_fail() {
+        let (temp_dir, mock_paths, workspace) = make_temp_workspace().unwrap();
+        workspace.initialize().unwrap();
+        fs::write(
+            mock_paths.config_path::<ConnectionConfig>(),
+            "host: mn2020\npermutation: p\ncookie: c\nprofile: uj\n",
+        )
+        .unwrap();
+
+        let result = ConnectionConfig::read_config(&workspace);
+
+        assert!(
+            matches!(&result, Err(Error::ProfileNotFound(x)) if x == "uj"),
+            "{:?}",
+            result
+        );
+        temp_dir.close().unwrap();
+    }
+
+    #[test]
+    fn password_command_should_take_precedence() {
+        let mut baca = make_baca();
+        baca.password = "pass".to_string();
+        assert_eq!(
+            baca.credential_provider(),
+            CredentialProvider::Plain("pass".to_string())
+        );
+
+        baca.password_command = Some("pass show uj/baca".to_string());
+        assert_eq!(
+            baca.credential_provider(),
+            CredentialProvider::Command("pass show uj/baca".to_string())
+        );
+
+        baca.password.clear();
+        baca.password_command = None;
+        assert_eq!(baca.credential_provider(), CredentialProvider::Prompt);
+    }
+
+    #[test]
+    fn overrides_should_take_precedence() {
+        let mut saved = make_baca();
+        saved.password_command = Some("pass show uj/baca".to_string());
+        let overrides = ConnectionOverrides {
+            host: Some("mn2020".to_string()),
+            password: Some("ci_pass".to_string()),
+            ..Default::default()
+        };
+
+        let config = overrides.apply(saved.clone());
+
+        assert_eq!(config.host, "mn2020");
+        assert_eq!(config.login, "test_login");
+        assert_eq!(
+            config.credential_provider(),
+            CredentialProvider::Plain("ci_pass".to_string())
+        );
+        assert_eq!(config.cookie, "test_cookie");
+    }
+
+    #[test]
+    fn overrides_should_not_be_saved() {
+        let mut saved = make_baca();
+        saved.password_command = Some("pass show uj/baca".to_string());
+        let overrides = ConnectionOverrides {
+            host: Some("mn2020".to_string()),
+            password: Some("ci_pass".to_string()),
+            cookie: Some("ci_cookie".to_string()),
+            ..Default::default()
+        };
+        let mut config = overrides.apply(saved.clone());
+        config.cookie = "new_cookie".to_string();
+
+        let config = overrides.restore(config, &saved);
+
+        assert_eq!(config.host, "test_host");
+        assert_eq!(config.password, "test_pass");
+        assert_eq!(config.password_command, saved.password_command);
+        assert_eq!(config.cookie, "new_cookie");
+    }
+
+    fn set_up_encryption<W: Workspace>(workspace: &W) {
+        let (encryption, key) = Encryption::with_rounds("passphrase", 10).unwrap();
+        remember(&workspace.get_paths(), &encryption, &key).unwrap();
+        UserConfig {
+            encryption: Some(encryption),
+            ..Default::default()
+        }
+        .save_config(workspace)
+        .unwrap();
+    }
+
+    /// The key is remembered in the user directory, as other tests replace the one of the process.
+    fn make_encrypted_workspace(user_dir: &Path) -> (TempDir, WorkspacePaths, WorkspaceDir) {
+        let temp_dir = TempDir::new().unwrap();
+        let paths =
+            WorkspacePaths::with_root(temp_dir.path()).with_user_dir(Some(user_dir.to_path_buf()));
+        let workspace = WorkspaceDir::with_paths(paths.clone());
+        workspace.initialize().unwrap();
+        (temp_dir, paths, workspace)
+    }
+
+    #[test]
+    fn encrypted_secrets_should_not_be_saved_in_plain_text() {
+        let user_dir = TempDir::new().unwrap();
+        let (temp_dir, mock_paths, workspace) = make_encrypted_workspace(user_dir.path());
+        set_up_encryption(&workspace);
+        let mut baca = make_baca();
+        baca.secrets = Some(String::new());
+
+        baca.save_config(&workspace).unwrap();
+
+        let saved = fs::read_to_string(mock_paths.config_path::<ConnectionConfig>()).unwrap();
+        assert!(!saved.contains("test_pass"), "{}", saved);
+        assert!(!saved.contains("test_cookie"), "{}", saved);
+        let read = ConnectionConfig::read_config(&workspace).unwrap();
+        assert_eq!(read.password, "test_pass");
+        assert_eq!(read.cookie, "test_cookie");
+        assert!(read.secrets.is_some());
+        temp_dir.close().unwrap();
+    }
+
+    #[test]
+    fn plaintext_secrets_should_be_encrypted_on_first_use() {
+        let user_dir = TempDir::new().unwrap();
+        let (temp_dir, mock_paths, workspace) = make_encrypted_workspace(user_dir.path());
+        make_baca().save_config(&workspace).unwrap();
+        assert!(!ConnectionConfig::encrypt_plaintext(&workspace).unwrap());
+
+        set_up_encryption(&workspace);
+
+        assert!(ConnectionConfig::encrypt_plaintext(&workspace).unwrap());
+        assert!(!ConnectionConfig::encrypt_plaintext(&workspace).unwrap());
+        let path = mock_paths.config_path::<ConnectionConfig>();
+        let saved = fs::read_to_string(&path).unwrap();
+        assert!(!saved.contains("test_pass"), "{}", saved);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = fs::metadata(&path).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+        assert_eq!(
+            ConnectionConfig::read_config(&workspace).unwrap().password,
+            "test_pass"
+        );
+        temp_dir.close().unwrap();
+    }
+
+    #[test]
+    fn encrypted_profile_password_should_be_shared_by_workspaces() {
+        let user_dir = TempDir::new().unwrap();
+        let (first_dir, _, first) = make_encrypted_workspace(user_dir.path());
+        let (second_dir, _, second) = make_encrypted_workspace(user_dir.path());
+        set_up_encryption(&first);
+        let mut user_config = UserConfig::read_config(&first).unwrap();
+        user_config.profiles.insert(
+            "uj".to_string(),
+            Profile {
+                login: "test_login".to_string(),
+                password: "test_pass".to_string(),
+                ..Default::default()
+            },
+        );
+        user_config.save_config(&first).unwrap();
+        let mut baca = make_baca();
+        baca.profile = Some("uj".to_string());
+        baca.secrets = Some(String::new());
+
+        baca.save_config(&second).unwrap();
+
+        let profiles = fs::read_to_string(user_dir.child("profiles").path()).unwrap();
+        assert!(!profiles.contains("test_pass"), "{}", profiles);
+        assert_eq!(
+            ConnectionConfig::read_config(&second).unwrap().password,
+            "test_pass"
+        );
+        first_dir.close().unwrap();
+        second_dir.close().unwrap();
+    }
+
+    #[test]
+    fn debug_output_should_mask_secrets() {
+        let baca = make_baca();
+
+        let output = format!("{:?}", baca);
+
+        assert!(output.contains("test_login"), "{}", output);
+        assert!(!output.contains("test_pass"), "{}", output);
+        assert!(!output.contains("test_cookie"), "{}", output);
+    }
     // todo: tests for removing and saving objects
 }
@@ -0,0 +1,92 @@
+use crate::error::{Error, Result};
+use crate::interactive;
+use std::process::Command;
+use tracing::{debug, info};
+
+/// Where the password comes from when logging in.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CredentialProvider {
+    /// Stored in plain text.
+    Plain(String),
+    /// Printed by a command, e.g. `pass show uj/baca`.
+    Command(String),
+    /// Asked for on every login.
+    Prompt,
+}
+
+impl CredentialProvider {
+    pub fn password(&self) -> Result<String> {
+        match self {
+            CredentialProvider::Plain(password) => Ok(password.clone()),
+            CredentialProvider::Command(command) => run_password_command(command),
+            CredentialProvider::Prompt => {
+                interactive::require("a password command with 'baca init --password-command'")?;
+                Ok(dialoguer::Password::new()
+                    .with_prompt("Password")
+                    .interact()?)
+            }
+        }
+    }
+}
+
+/// Returns the first line printed by `command`.
+fn run_password_command(command: &str) -> Result<String> {
+    info!("Running password command: {}", command);
+
+    let output = match cfg!(windows) {
+        true => Command::new("cmd").args(["/C", command]).output(),
+        false => Command::new("sh").args(["-c", command]).output(),
+    }
+    .map_err(|e| Error::PasswordCommandFailed(command.to_string(), e.to_string()))?;
+    debug!("Password command status: {}", output.status);
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+        return Err(Error::PasswordCommandFailed(command.to_string(), stderr));
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    match stdout.lines().next() {
+        Some(password) if !password.is_empty() => Ok(password.to_string()),
+        _ => Err(Error::PasswordCommandFailed(
+            command.to_string(),
+            "no password printed".to_string(),
+        )),
+    }
+}
+
+#[cfg(test)]
+#[cfg(unix)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn command_password_should_be_first_line_of_output() {
+        let provider = CredentialProvider::Command("printf 'PaSsWorD\\nurl: baca'".to_string());
+
+        assert_eq!(provider.password().unwrap(), "PaSsWorD");
+    }
+
+    #[test]
+    fn failing_command_should_report_its_error() {
+        let provider = CredentialProvider::Command("echo 'not in store' >&2; exit 1".to_string());
+
+        let result = provider.password();
+
+        assert!(
+            matches!(&result, Err(Error::PasswordCommandFailed(_, e)) if e == "not in store"),
+            "{:?}",
+            result
+        );
+    }
+
+    #[test]
+    fn command_without_output_should_fail() {
+        let provider = CredentialProvider::Command("true".to_string());
+
+        assert!(matches!(
+            provider.password(),
+            Err(Error::PasswordCommandFailed(_, _))
+        ));
+    }
+}
@@ -1,29 +1,45 @@
 use crate::error::*;
 use crate::model::Language;
 use std::fmt::Debug;
-use std::fs::File;
-use std::io::{BufRead, BufReader};
+use std::fs;
 use std::path::Path;
 use tracing::{debug, info};
 
 pub fn is_header_present<P>(input_file: P, lang: &Language) -> Result<bool>
+where
+    P: AsRef<Path> + Debug,
+{
+    let r = read_header(input_file, lang)?.is_some();
+    info!("Header found: {r}");
+    Ok(r)
+}
+
+/// Returns the header: a comment in the first line or, for C-family languages, a block comment
+/// starting in the first line.
+pub fn read_header<P>(input_file: P, lang: &Language) -> Result<Option<String>>
 where
     P: AsRef<Path> + Debug,
 {
     info!("Checking for header...");
     debug!("Checking for {:?} header in file {:?}", lang, input_file);
 
-    let input_file = File::open(input_file)?;
-    let first_line = BufReader::new(input_file)
-        .lines()
-        .take(1)
-        .map(|x| x.unwrap_or_else(|_| "no_header".to_string()))
-        .collect::<String>();
-
+    let content = fs::read(input_file)?;
+    let content = String::from_utf8_lossy(&content);
+    let first_line = content.lines().next().unwrap_or_default();
     debug!("First line: {first_line}");
-    let r = lang.is_comment(&first_line);
-    info!("Header found: {r}");
-    Ok(r)
+
+    if lang.is_comment(first_line) {
+        return Ok(Some(first_line.to_string()));
+    }
+
+    Ok(lang
+        .block_comment_style()
+        .filter(|(begin, _)| first_line.starts_with(begin))
+        .and_then(|(begin, end)| {
+            content[begin.len()..]
+                .find(end)
+                .map(|x| content[..begin.len() + x + end.len()].to_string())
+        }))
 }
 
 #[cfg(test)]
@@ -88,4 +104,28 @@ return 5;
 
         assert!(is_header_present(input.path(), &Language::Cpp).unwrap());
     }
+
+    #[test]
+    fn block_comment_header_present() {
+        let input = make_input_file("/* Hubert Jaremko\n * hjaremko\n */\nint main() {}\n");
+
+        assert_eq!(
+            read_header(input.path(), &Language::Java).unwrap().unwrap(),
+            "/* Hubert Jaremko\n * hjaremko\n */"
+        );
+    }
+
+    #[test]
+    fn unterminated_block_comment_is_not_header() {
+        let input = make_input_file("/* Hubert Jaremko\nint main() {}\n");
+
+        assert!(!(is_header_present(input.path(), &Language::Cpp).unwrap()));
+    }
+
+    #[test]
+    fn block_comment_is_not_bash_header() {
+        let input = make_input_file("/* Hubert Jaremko */\necho 1\n");
+
+        assert!(!(is_header_present(input.path(), &Language::Bash).unwrap()));
+    }
 }
@@ -0,0 +1,277 @@
+use crate::error::*;
+use colored::Colorize;
+use regex::Regex;
+use std::collections::HashSet;
+use std::fs;
+use std::fs::File;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use tracing::{debug, info};
+
+/// Replaces quoted `#include` directives of `input_file` with the content of the included
+/// headers, looked up relative to `include_dir` and to the including header.
+pub fn inline_includes<P, Q, R>(input_file: P, include_dir: Q, output_dir: R) -> Result<PathBuf>
+where
+    P: AsRef<Path>,
+    Q: AsRef<Path>,
+    R: AsRef<Path>,
+{
+    let input_file: &Path = input_file.as_ref();
+    info!("Inlining local includes into {:?}", input_file);
+
+    let content = fs::read_to_string(input_file)?;
+    let content = Inliner::new().inline(&content, include_dir.as_ref())?;
+
+    let filepath = output_dir
+        .as_ref()
+        .join(input_file.file_name().ok_or(Error::InputFileDoesNotExist)?);
+    let mut file = File::create(filepath.clone())?;
+    file.write_all(content.as_ref())?;
+
+    debug!("New input file path: {:?}", filepath);
+    debug!("New input file content:\n{}", content);
+
+    Ok(filepath)
+}
+
+struct Inliner {
+    include_re: Regex,
+    pragma_once_re: Regex,
+    guard_re: Regex,
+    /// Headers with `#pragma once` or include guards, which were already inlined.
+    included_once: HashSet<PathBuf>,
+    /// Headers being inlined right now, used to detect cycles.
+    stack: Vec<PathBuf>,
+}
+
+impl Inliner {
+    fn new() -> Self {
+        Self {
+            include_re: Regex::new(r#"^\s*#\s*include\s*"([^"]+)""#).unwrap(),
+            pragma_once_re: Regex::new(r#"^\s*#\s*pragma\s+once\b"#).unwrap(),
+            guard_re: Regex::new(r#"(?m)^\s*#\s*ifndef\s+(\w+)\s*$\s*^\s*#\s*define\s+(\w+)\b"#)
+                .unwrap(),
+            included_once: HashSet::new(),
+            stack: Vec::new(),
+        }
+    }
+
+    fn inline(&mut self, content: &str, dir: &Path) -> Result<String> {
+        let mut result = String::with_capacity(content.len());
+
+        for line in content.split_inclusive('\n') {
+            if self.pragma_once_re.is_match(line) {
+                continue;
+            }
+
+            let header = match self.include_re.captures(line) {
+                Some(captures) => dir.join(&captures[1]),
+                None => {
+                    result.push_str(line);
+                    continue;
+                }
+            };
+
+            if !header.is_file() {
+                println!(
+                    "{}",
+                    format!("Local include {:?} not found, leaving it as is.", header)
+                        .bright_yellow()
+                );
+                result.push_str(line);
+                continue;
+            }
+
+            result.push_str(&self.inline_header(&header)?);
+        }
+
+        Ok(result)
+    }
+
+    fn inline_header(&mut self, header: &Path) -> Result<String> {
+        let header = header.canonicalize()?;
+
+        if self.included_once.contains(&header) {
+            debug!("{:?} already included", header);
+            return Ok(String::new());
+        }
+
+        if self.stack.contains(&header) {
+            let mut cycle = self
+                .stack
+                .iter()
+                .skip_while(|x| **x != header)
+                .map(|x| file_name(x))
+                .collect::<Vec<_>>();
+            cycle.push(file_name(&header));
+            return Err(Error::IncludeCycle(cycle.join(" -> ")));
+        }
+
+        let content = fs::read_to_string(&header)?;
+
+        if self.is_include_once(&content) {
+            self.included_once.insert(header.clone());
+        }
+
+        self.stack.push(header.clone());
+        let dir = header.parent().unwrap_or_else(|| Path::new(""));
+        let mut inlined = self.inline(&content, dir)?;
+        self.stack.pop();
+
+        if !inlined.is_empty() && !inlined.ends_with('\n') {
+            inlined.push('\n');
+        }
+
+        Ok(inlined)
+    }
+
+    fn is_include_once(&self, content: &str) -> bool {
+        if content.lines().any(|x| self.pragma_once_re.is_match(x)) {
+            return true;
+        }
+
+        self.guard_re
+            .captures(content)
+            .is_some_and(|x| x[1] == x[2])
+    }
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_name()
+        .map(|x| x.to_string_lossy().to_string())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert_fs::prelude::*;
+    use assert_fs::TempDir;
+
+    fn inline_str(dir: &TempDir, content: &str) -> Result<String> {
+        Inliner::new().inline(content, dir.path())
+    }
+
+    #[test]
+    fn system_includes_should_be_untouched() {
+        let dir = TempDir::new().unwrap();
+        let input = "// Hubert Jaremko\n#include <vector>\nint main() {}\n";
+
+        assert_eq!(inline_str(&dir, input).unwrap(), input);
+    }
+
+    #[test]
+    fn local_include_should_be_inlined_after_header() {
+        let dir = TempDir::new().unwrap();
+        dir.child("matrix.h")
+            .write_str("#pragma once\nstruct Matrix {};")
+            .unwrap();
+
+        let actual = inline_str(
+            &dir,
+            "// Hubert Jaremko\n#include \"matrix.h\"\nint main() {}\n",
+        )
+        .unwrap();
+
+        assert_eq!(
+            actual,
+            "// Hubert Jaremko\nstruct Matrix {};\nint main() {}\n"
+        );
+    }
+
+    #[test]
+    fn nested_includes_should_be_relative_to_header() {
+        let dir = TempDir::new().unwrap();
+        dir.child("lib/vector.h")
+            .write_str("#include \"detail/alloc.h\"\nstruct Vector {};\n")
+            .unwrap();
+        dir.child("lib/detail/alloc.h")
+            .write_str("struct Alloc {};\n")
+            .unwrap();
+
+        let actual = inline_str(&dir, "#include \"lib/vector.h\"\n").unwrap();
+
+        assert_eq!(actual, "struct Alloc {};\nstruct Vector {};\n");
+    }
+
+    #[test]
+    fn guarded_header_should_be_inlined_once() {
+        let dir = TempDir::new().unwrap();
+        dir.child("a.h")
+            .write_str("#ifndef A_H\n#define A_H\nstruct A {};\n#endif\n")
+            .unwrap();
+        dir.child("b.h")
+            .write_str("#pragma once\n#include \"a.h\"\nstruct B {};\n")
+            .unwrap();
+
+        let actual = inline_str(
+            &dir,
+            "#include \"a.h\"\n#include \"b.h\"\n#include \"b.h\"\n",
+        )
+        .unwrap();
+
+        assert_eq!(
+            actual,
+            "#ifndef A_H\n#define A_H\nstruct A {};\n#endif\nstruct B {};\n"
+        );
+    }
+
+    #[test]
+    fn unguarded_cycle_should_fail() {
+        let dir = TempDir::new().unwrap();
+        dir.child("a.h").write_str("#include \"b.h\"\n").unwrap();
+        dir.child("b.h").write_str("#include \"a.h\"\n").unwrap();
+
+        let result = inline_str(&dir, "#include \"a.h\"\n");
+
+        assert!(
+            matches!(&result, Err(Error::IncludeCycle(cycle)) if cycle == "a.h -> b.h -> a.h"),
+            "{:?}",
+            result
+        );
+    }
+
+    #[test]
+    fn guarded_cycle_should_be_inlined() {
+        let dir = TempDir::new().unwrap();
+        dir.child("a.h")
+            .write_str("#pragma once\n#include \"b.h\"\nstruct A {};\n")
+            .unwrap();
+        dir.child("b.h")
+            .write_str("#pragma once\n#include \"a.h\"\nstruct B {};\n")
+            .unwrap();
+
+        let actual = inline_str(&dir, "#include \"a.h\"\n").unwrap();
+
+        assert_eq!(actual, "struct B {};\nstruct A {};\n");
+    }
+
+    #[test]
+    fn missing_local_include_should_be_kept() {
+        let dir = TempDir::new().unwrap();
+        let input = "#include \"missing.h\"\n";
+
+        assert_eq!(inline_str(&dir, input).unwrap(), input);
+    }
+
+    #[test]
+    fn saved_file_should_contain_inlined_content() {
+        let dir = TempDir::new().unwrap();
+        let output_dir = TempDir::new().unwrap();
+        dir.child("util.h")
+            .write_str("int add(int, int);\n")
+            .unwrap();
+        let input = dir.child("main.cpp");
+        input
+            .write_str("// Hubert Jaremko\n#include \"util.h\"\n")
+            .unwrap();
+
+        let actual = inline_includes(input.path(), dir.path(), output_dir.path()).unwrap();
+
+        assert_eq!(actual, output_dir.join("main.cpp"));
+        assert_eq!(
+            fs::read_to_string(actual).unwrap(),
+            "// Hubert Jaremko\nint add(int, int);\n"
+        );
+    }
+}
@@ -0,0 +1,197 @@
+use crate::error::{Error, Result};
+use crate::model::Language;
+use crate::workspace::{ConfigObject, Workspace, WorkspacePaths};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::path::{Path, PathBuf};
+use time::OffsetDateTime;
+use tracing::debug;
+
+/// Submit id recorded for a file which was sent, but did not show up in the results.
+pub const PENDING_SUBMIT_ID: &str = "pending";
+
+/// A single submit sent to BaCa.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct JournalEntry {
+    #[serde(with = "time::serde::rfc3339")]
+    pub timestamp: OffsetDateTime,
+    pub task_id: String,
+    pub task_name: String,
+    pub language: Language,
+    /// Files as selected by the user.
+    pub files: Vec<PathBuf>,
+    pub transforms: Vec<String>,
+    /// SHA-256 of the submitted file.
+    pub sha256: String,
+    pub submit_id: String,
+    /// Copy of the exact submitted file.
+    pub snapshot: PathBuf,
+}
+
+impl JournalEntry {
+    /// The file was sent, but the submit did not show up in the results, so its id is unknown.
+    pub fn is_pending(&self) -> bool {
+        self.submit_id == PENDING_SUBMIT_ID
+    }
+
+    fn snapshot_dir(&self) -> String {
+        match self.is_pending() {
+            true => format!("{}-{}", PENDING_SUBMIT_ID, self.timestamp.unix_timestamp()),
+            false => self.submit_id.clone(),
+        }
+    }
+}
+
+/// Local record of all submits made from the workspace, oldest first.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Journal {
+    #[serde(default)]
+    pub entries: Vec<JournalEntry>,
+}
+
+impl Journal {
+    /// Keeps a copy of `artifact` under the submit id and appends `entry` pointing to it.
+    pub fn record<W: Workspace>(
+        workspace: &W,
+        mut entry: JournalEntry,
+        artifact: &Path,
+    ) -> Result<()> {
+        entry.snapshot = save_snapshot(&workspace.get_paths(), &entry.snapshot_dir(), artifact)?;
+
+        let mut journal = Self::read_config(workspace)?;
+        journal.entries.push(entry);
+        journal.save_config(workspace)
+    }
+}
+
+impl ConfigObject for Journal {
+    fn save_config<W: Workspace>(&self, workspace: &W) -> Result<()> {
+        workspace.save_config_object(self)
+    }
+
+    fn read_config<W: Workspace>(workspace: &W) -> Result<Self> {
+        match workspace.read_config_object::<Self>() {
+            Err(Error::ReadingConfig(_)) => Ok(Self::default()),
+            result => result,
+        }
+    }
+
+    fn remove_config<W: Workspace>(workspace: &W) -> Result<()> {
+        workspace.remove_config_object::<Self>()
+    }
+
+    fn config_filename() -> String {
+        "journal".to_string()
+    }
+}
+
+pub fn hash_file(path: &Path) -> Result<String> {
+    let content = fs::read(path)?;
+    Ok(format!("{:x}", Sha256::digest(content)))
+}
+
+fn save_snapshot(paths: &WorkspacePaths, dir_name: &str, artifact: &Path) -> Result<PathBuf> {
+    let dir = paths.submissions_dir().join(dir_name);
+    fs::create_dir_all(&dir)?;
+
+    let snapshot = dir.join(artifact.file_name().ok_or(Error::InputFileDoesNotExist)?);
+    fs::copy(artifact, &snapshot)?;
+    debug!("Submit snapshot: {:?}", snapshot);
+
+    Ok(snapshot)
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crate::workspace::workspace_dir::tests::make_temp_workspace;
+    use assert_fs::prelude::*;
+
+    pub fn make_entry(task_id: &str, submit_id: &str) -> JournalEntry {
+        JournalEntry {
+            timestamp: OffsetDateTime::from_unix_timestamp(1_700_000_000).unwrap(),
+            task_id: task_id.to_string(),
+            task_name: "[A] Zera funkcji".to_string(),
+            language: Language::Cpp,
+            files: vec![PathBuf::from("/home/user/zera/main.cpp")],
+            transforms: vec!["no-main".to_string()],
+            sha256: "abc".to_string(),
+            submit_id: submit_id.to_string(),
+            snapshot: PathBuf::new(),
+        }
+    }
+
+    #[test]
+    fn hash_should_be_sha256() {
+        let dir = assert_fs::TempDir::new().unwrap();
+        let file = dir.child("source.cpp");
+        file.write_str("abc").unwrap();
+
+        assert_eq!(
+            hash_file(file.path()).unwrap(),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+    }
+
+    #[test]
+    fn recorded_submits_should_be_appended_with_snapshots() {
+        let (temp_dir, mock_paths, workspace) = make_temp_workspace().unwrap();
+        workspace.initialize().unwrap();
+        let artifact = temp_dir.child("source.cpp");
+        artifact.write_str("// Hubert Jaremko\n").unwrap();
+
+        Journal::record(&workspace, make_entry("1", "4242"), artifact.path()).unwrap();
+        Journal::record(&workspace, make_entry("2", "4243"), artifact.path()).unwrap();
+
+        let journal = Journal::read_config(&workspace).unwrap();
+        let ids = journal
+            .entries
+            .iter()
+            .map(|x| x.submit_id.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(ids, vec!["4242", "4243"]);
+
+        let snapshot = &journal.entries[0].snapshot;
+        assert_eq!(
+            *snapshot,
+            mock_paths.submissions_dir().join("4242/source.cpp")
+        );
+        assert_eq!(fs::read_to_string(snapshot).unwrap(), "// Hubert Jaremko\n");
+        temp_dir.close().unwrap();
+    }
+
+    #[test]
+    fn pending_submits_should_keep_separate_snapshots() {
+        let (temp_dir, mock_paths, workspace) = make_temp_workspace().unwrap();
+        workspace.initialize().unwrap();
+        let artifact = temp_dir.child("source.cpp");
+        artifact.write_str("// Hubert Jaremko\n").unwrap();
+        let mut later = make_entry("1", PENDING_SUBMIT_ID);
+        later.timestamp = OffsetDateTime::from_unix_timestamp(1_700_000_060).unwrap();
+
+        Journal::record(
+            &workspace,
+            make_entry("1", PENDING_SUBMIT_ID),
+            artifact.path(),
+        )
+        .unwrap();
+        Journal::record(&workspace, later, artifact.path()).unwrap();
+
+        let journal = Journal::read_config(&workspace).unwrap();
+        assert!(journal.entries.iter().all(|x| x.is_pending()));
+        assert_eq!(
+            journal.entries[0].snapshot,
+            mock_paths
+                .submissions_dir()
+                .join("pending-1700000000/source.cpp")
+        );
+        assert_eq!(
+            journal.entries[1].snapshot,
+            mock_paths
+                .submissions_dir()
+                .join("pending-1700000060/source.cpp")
+        );
+        temp_dir.close().unwrap();
+    }
+}
@@ -0,0 +1,201 @@
+use crate::model::Language;
+use std::ops::Range;
+
+/// Part of a source file, as seen by the transforms and lint rules.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Segment {
+    Code,
+    Comment,
+    Literal,
+}
+
+struct Syntax {
+    line_comment: Option<&'static str>,
+    block_comment: Option<(&'static str, &'static str)>,
+    /// Quote characters and whether backslash escapes them.
+    quotes: &'static [(char, bool)],
+    /// Bash comments start only at the beginning of a word.
+    comment_starts_word: bool,
+    raw_strings: bool,
+    text_blocks: bool,
+}
+
+impl Syntax {
+    fn of(language: &Language) -> Self {
+        let none = Syntax {
+            line_comment: None,
+            block_comment: None,
+            quotes: &[],
+            comment_starts_word: false,
+            raw_strings: false,
+            text_blocks: false,
+        };
+
+        match language {
+            Language::Cpp | Language::CppWithFileSupport | Language::Java => Syntax {
+                line_comment: Some("//"),
+                block_comment: Some(("/*", "*/")),
+                quotes: &[('"', true), ('\'', true)],
+                raw_strings: *language != Language::Java,
+                text_blocks: *language == Language::Java,
+                ..none
+            },
+            Language::Bash => Syntax {
+                line_comment: Some("#"),
+                quotes: &[('"', true), ('\'', false)],
+                comment_starts_word: true,
+                ..none
+            },
+            Language::Ada => Syntax {
+                line_comment: Some("--"),
+                quotes: &[('"', false)],
+                ..none
+            },
+            Language::Unsupported => none,
+        }
+    }
+}
+
+/// Splits `content` into code, comments and string or character literals.
+pub fn split_segments(content: &str, language: &Language) -> Vec<(Segment, Range<usize>)> {
+    let syntax = Syntax::of(language);
+    let mut segments = Vec::new();
+    let mut code_start = 0;
+    let mut i = 0;
+
+    while i < content.len() {
+        let rest = &content[i..];
+        let previous = content[..i].chars().next_back();
+
+        let found = if let Some(end) = match_comment(rest, previous, &syntax) {
+            Some((Segment::Comment, end))
+        } else {
+            match_literal(content, i, &syntax).map(|end| (Segment::Literal, end))
+        };
+
+        match found {
+            Some((segment, end)) => {
+                if code_start < i {
+                    segments.push((Segment::Code, code_start..i));
+                }
+                segments.push((segment, i..i + end));
+                i += end;
+                code_start = i;
+            }
+            None => i += rest.chars().next().map_or(1, char::len_utf8),
+        }
+    }
+
+    if code_start < content.len() {
+        segments.push((Segment::Code, code_start..content.len()));
+    }
+
+    segments
+}
+
+/// Length of the comment starting at the beginning of `rest`.
+fn match_comment(rest: &str, previous: Option<char>, syntax: &Syntax) -> Option<usize> {
+    if let Some(style) = syntax.line_comment {
+        let starts_word = previous.is_none_or(char::is_whitespace);
+
+        if rest.starts_with(style) && (starts_word || !syntax.comment_starts_word) {
+            return Some(rest.find('\n').unwrap_or(rest.len()));
+        }
+    }
+
+    let (begin, end) = syntax.block_comment?;
+    rest.strip_prefix(begin).map(|body| {
+        body.find(end)
+            .map_or(rest.len(), |x| begin.len() + x + end.len())
+    })
+}
+
+/// Length of the literal starting at byte `start` of `content`.
+fn match_literal(content: &str, start: usize, syntax: &Syntax) -> Option<usize> {
+    let rest = &content[start..];
+    let previous = content[..start].chars().next_back();
+
+    if syntax.text_blocks {
+        if let Some(body) = rest.strip_prefix(r#"""""#) {
+            return Some(body.find(r#"""""#).map_or(rest.len(), |x| x + 6));
+        }
+    }
+
+    if syntax.raw_strings && rest.starts_with("R\"") && is_raw_string_prefix(&content[..start]) {
+        let delimiter = &rest[2..rest.find('(')?];
+        let terminator = format!("){}\"", delimiter);
+        let body = &rest[2 + delimiter.len() + 1..];
+
+        return Some(body.find(&terminator).map_or(rest.len(), |x| {
+            rest.len() - body.len() + x + terminator.len()
+        }));
+    }
+
+    let quote = rest.chars().next()?;
+    let (_, escapes) = syntax.quotes.iter().find(|(x, _)| *x == quote)?;
+
+    // C++14 digit separators, like 1'000'000.
+    if quote == '\'' && previous.is_some_and(|x| x.is_ascii_alphanumeric()) && syntax.raw_strings {
+        return None;
+    }
+
+    let mut chars = rest.char_indices().skip(1);
+    while let Some((index, c)) = chars.next() {
+        match c {
+            '\\' if *escapes => {
+                chars.next();
+            }
+            '\n' if quote == '\'' && *escapes => return Some(index),
+            c if c == quote => return Some(index + c.len_utf8()),
+            _ => {}
+        }
+    }
+
+    Some(rest.len())
+}
+
+/// Raw strings may be prefixed with an encoding, like `u8R"(...)"`.
+fn is_raw_string_prefix(before: &str) -> bool {
+    let identifier = before
+        .rsplit(|x: char| !x.is_ascii_alphanumeric() && x != '_')
+        .next()
+        .unwrap_or_default();
+
+    matches!(identifier, "" | "u8" | "u" | "U" | "L")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kinds<'a>(content: &'a str, language: &Language) -> Vec<(Segment, &'a str)> {
+        split_segments(content, language)
+            .into_iter()
+            .map(|(segment, range)| (segment, &content[range]))
+            .collect()
+    }
+
+    #[test]
+    fn comments_and_literals_should_be_split_from_code() {
+        assert_eq!(
+            kinds("int x = '{'; // }\nauto s = u8R\"x(\")x\";", &Language::Cpp),
+            vec![
+                (Segment::Code, "int x = "),
+                (Segment::Literal, "'{'"),
+                (Segment::Code, "; "),
+                (Segment::Comment, "// }"),
+                (Segment::Code, "\nauto s = u8"),
+                (Segment::Literal, "R\"x(\")x\""),
+                (Segment::Code, ";"),
+            ]
+        );
+    }
+
+    #[test]
+    fn digit_separators_should_stay_in_code() {
+        assert_eq!(
+            kinds("int x = 1'000;", &Language::Cpp),
+            vec![(Segment::Code, "int x = 1'000;")]
+        );
+    }
+}
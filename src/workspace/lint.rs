@@ -0,0 +1,400 @@
+use crate::error::*;
+use crate::model::Language;
+use crate::workspace::lexer::{split_segments, Segment};
+use crate::workspace::LintRules;
+use regex::Regex;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+use tracing::{debug, info};
+
+/// A single broken lint rule.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Violation {
+    pub file: String,
+    pub line: Option<usize>,
+    pub message: String,
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.line {
+            Some(line) => write!(f, "{}:{}: {}", self.file, line, self.message),
+            None => write!(f, "{}: {}", self.file, self.message),
+        }
+    }
+}
+
+/// Checks the `submitted` file against `rules`, as the rules apply to what is sent.
+///
+/// Lines found unchanged in `original` are reported as its lines under `name`, so they match
+/// what the user wrote. Lines added by the transforms, like inlined headers, are reported
+/// as lines of the `submitted` file.
+pub fn lint_file<P, Q>(
+    original: P,
+    submitted: Q,
+    name: &str,
+    language: &Language,
+    rules: &LintRules,
+) -> Result<Vec<Violation>>
+where
+    P: AsRef<Path>,
+    Q: AsRef<Path>,
+{
+    let submitted = submitted.as_ref();
+    info!("Linting {:?}", submitted);
+
+    let bytes = fs::read(submitted)?;
+    let content = String::from_utf8_lossy(&bytes);
+    let mut found = find_violations(&content, language, rules)?;
+    found.sort_by_key(|(line, _)| *line);
+
+    let original = fs::read(original)?;
+    let original = String::from_utf8_lossy(&original);
+    let original_lines = original.lines().collect::<Vec<_>>();
+    let submitted_lines = content.lines().collect::<Vec<_>>();
+
+    let mut violations = Vec::new();
+    let size = bytes.len() as u64;
+    if let Some(max_size) = rules.max_size.filter(|x| size > *x) {
+        violations.push(Violation {
+            file: name.to_string(),
+            line: None,
+            message: format!(
+                "submitted file has {} bytes, the limit is {}",
+                size, max_size
+            ),
+        });
+    }
+
+    for (line, message) in found {
+        let violation = match map_line(&submitted_lines, &original_lines, line) {
+            Some(line) => Violation {
+                file: name.to_string(),
+                line: Some(line),
+                message,
+            },
+            None => Violation {
+                file: submitted.to_string_lossy().to_string(),
+                line: Some(line),
+                message,
+            },
+        };
+        violations.push(violation);
+    }
+
+    debug!("Violations: {:?}", violations);
+    Ok(violations)
+}
+
+/// Returns the 1-based lines breaking the rules, with the reason.
+fn find_violations(
+    content: &str,
+    language: &Language,
+    rules: &LintRules,
+) -> Result<Vec<(usize, String)>> {
+    let mut violations = Vec::new();
+    let segments = split_segments(content, language);
+    let without_comments = mask(content, &segments, |x| x == Segment::Comment);
+    let code = mask(content, &segments, |x| x != Segment::Code);
+
+    for (line, include) in
+        find_forbidden_includes(&without_comments, language, &rules.forbidden_includes)
+    {
+        violations.push((line, format!("forbidden include '{}'", include)));
+    }
+
+    for identifier in &rules.forbidden_identifiers {
+        let re = make_identifier_regex(identifier);
+
+        for (number, line) in code.lines().enumerate() {
+            if re.is_match(line) {
+                violations.push((number + 1, format!("forbidden identifier '{}'", identifier)));
+            }
+        }
+    }
+
+    for pattern in &rules.forbidden_patterns {
+        let re = Regex::new(pattern).map_err(|_| Error::InvalidPattern(pattern.clone()))?;
+
+        for (number, line) in content.lines().enumerate() {
+            if re.is_match(line) {
+                violations.push((number + 1, format!("forbidden pattern '{}'", pattern)));
+            }
+        }
+    }
+
+    Ok(violations)
+}
+
+/// Finds the submitted `line` in the original file: the n-th occurrence of its text there
+/// for its n-th occurrence in the submitted file.
+fn map_line(submitted: &[&str], original: &[&str], line: usize) -> Option<usize> {
+    let text = submitted.get(line - 1)?;
+    let occurrence = submitted[..line - 1].iter().filter(|x| *x == text).count();
+
+    original
+        .iter()
+        .enumerate()
+        .filter(|(_, x)| *x == text)
+        .nth(occurrence)
+        .map(|(number, _)| number + 1)
+}
+
+/// Replaces the selected segments with spaces, keeping the line numbers.
+fn mask<F>(content: &str, segments: &[(Segment, std::ops::Range<usize>)], masked: F) -> String
+where
+    F: Fn(Segment) -> bool,
+{
+    segments
+        .iter()
+        .map(|(segment, range)| {
+            let text = &content[range.clone()];
+            match masked(*segment) {
+                true => text
+                    .chars()
+                    .map(|x| if x == '\n' { '\n' } else { ' ' })
+                    .collect(),
+                false => text.to_string(),
+            }
+        })
+        .collect()
+}
+
+/// Returns the lines including forbidden headers, or importing forbidden Java packages.
+fn find_forbidden_includes(
+    content: &str,
+    language: &Language,
+    forbidden: &[String],
+) -> Vec<(usize, String)> {
+    let re = match language {
+        Language::Cpp | Language::CppWithFileSupport => {
+            Regex::new(r#"^\s*#\s*include\s*[<"]([^>"]+)[>"]"#).unwrap()
+        }
+        Language::Java => Regex::new(r"^\s*import\s+(?:static\s+)?([\w.]+(?:\.\*)?)\s*;").unwrap(),
+        _ => return Vec::new(),
+    };
+
+    // Forbidding a package forbids its classes as well.
+    let is_forbidden = |included: &str, rule: &str| {
+        included == rule
+            || (*language == Language::Java
+                && included
+                    .strip_prefix(rule)
+                    .is_some_and(|x| x.starts_with('.')))
+    };
+
+    content
+        .lines()
+        .enumerate()
+        .filter_map(|(number, line)| {
+            let included = re.captures(line)?[1].trim().to_string();
+            forbidden
+                .iter()
+                .any(|x| is_forbidden(&included, x))
+                .then_some((number + 1, included))
+        })
+        .collect()
+}
+
+fn make_identifier_regex(identifier: &str) -> Regex {
+    let is_word = |x: Option<char>| x.is_some_and(|x| x.is_alphanumeric() || x == '_');
+    let start = if is_word(identifier.chars().next()) {
+        r"\b"
+    } else {
+        ""
+    };
+    let end = if is_word(identifier.chars().last()) {
+        r"\b"
+    } else {
+        ""
+    };
+
+    Regex::new(&format!("{}{}{}", start, regex::escape(identifier), end)).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert_fs::prelude::*;
+    use assert_fs::TempDir;
+
+    fn lint_str(content: &str, language: &Language, rules: &LintRules) -> Result<Vec<String>> {
+        let dir = TempDir::new().unwrap();
+        let input = dir.child("main.cpp");
+        input.write_str(content).unwrap();
+
+        lint_file(input.path(), input.path(), "main.cpp", language, rules)
+            .map(|x| x.iter().map(|x| x.to_string()).collect())
+    }
+
+    #[test]
+    fn forbidden_includes_should_be_reported() {
+        let rules = LintRules {
+            forbidden_includes: vec!["vector".to_string(), "algorithm".to_string()],
+            ..Default::default()
+        };
+        let content = "// #include <vector>\n#include <vector>\n# include \"algorithm\"\n#include <iostream>\n";
+
+        assert_eq!(
+            lint_str(content, &Language::Cpp, &rules).unwrap(),
+            vec![
+                "main.cpp:2: forbidden include 'vector'",
+                "main.cpp:3: forbidden include 'algorithm'"
+            ]
+        );
+    }
+
+    #[test]
+    fn forbidden_java_packages_should_be_reported() {
+        let rules = LintRules {
+            forbidden_includes: vec!["java.util".to_string()],
+            ..Default::default()
+        };
+        let content =
+            "import java.util.ArrayList;\nimport java.utility.Foo;\nimport java.util.*;\n";
+
+        assert_eq!(
+            lint_str(content, &Language::Java, &rules).unwrap(),
+            vec![
+                "main.cpp:1: forbidden include 'java.util.ArrayList'",
+                "main.cpp:3: forbidden include 'java.util.*'"
+            ]
+        );
+    }
+
+    #[test]
+    fn identifiers_in_comments_and_strings_should_be_ignored() {
+        let rules = LintRules {
+            forbidden_identifiers: vec!["goto".to_string(), "std::sort".to_string()],
+            ..Default::default()
+        };
+        let content =
+            "/* goto\n std::sort */ int gotoX;\nputs(\"goto\");\ngoto end;\nstd::sort(a, b);\n";
+
+        assert_eq!(
+            lint_str(content, &Language::Cpp, &rules).unwrap(),
+            vec![
+                "main.cpp:4: forbidden identifier 'goto'",
+                "main.cpp:5: forbidden identifier 'std::sort'"
+            ]
+        );
+    }
+
+    #[test]
+    fn forbidden_patterns_should_match_lines() {
+        let rules = LintRules {
+            forbidden_patterns: vec![r"^int \w+;".to_string()],
+            ..Default::default()
+        };
+
+        assert_eq!(
+            lint_str(
+                "int global;\nint main() {\n    int local;\n}\n",
+                &Language::Cpp,
+                &rules
+            )
+            .unwrap(),
+            vec!["main.cpp:1: forbidden pattern '^int \\w+;'"]
+        );
+    }
+
+    #[test]
+    fn invalid_pattern_should_fail() {
+        let rules = LintRules {
+            forbidden_patterns: vec!["(".to_string()],
+            ..Default::default()
+        };
+
+        let result = lint_str("", &Language::Cpp, &rules);
+
+        assert!(
+            matches!(result, Err(Error::InvalidPattern(_))),
+            "{:?}",
+            result
+        );
+    }
+
+    #[test]
+    fn too_large_file_should_be_reported() {
+        let rules = LintRules {
+            max_size: Some(4),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            lint_str("int x;\n", &Language::Cpp, &rules).unwrap(),
+            vec!["main.cpp: submitted file has 7 bytes, the limit is 4"]
+        );
+    }
+
+    #[test]
+    fn lines_should_refer_to_original_file() {
+        let dir = TempDir::new().unwrap();
+        let original = dir.child("main.cpp");
+        original
+            .write_str("int main() {\n    goto end;\n}\n")
+            .unwrap();
+        let submitted = dir.child("staged.cpp");
+        submitted
+            .write_str("// Hubert Jaremko\n// [A] Zera funkcji\nint main() {\n    goto end;\n}\n")
+            .unwrap();
+        let rules = LintRules {
+            forbidden_identifiers: vec!["goto".to_string()],
+            max_size: Some(40),
+            ..Default::default()
+        };
+
+        let violations = lint_file(
+            original.path(),
+            submitted.path(),
+            "main.cpp",
+            &Language::Cpp,
+            &rules,
+        )
+        .unwrap();
+
+        assert_eq!(
+            violations.iter().map(|x| x.to_string()).collect::<Vec<_>>(),
+            vec![
+                "main.cpp: submitted file has 67 bytes, the limit is 40",
+                "main.cpp:2: forbidden identifier 'goto'",
+            ]
+        );
+    }
+
+    #[test]
+    fn code_added_by_transforms_should_refer_to_submitted_file() {
+        let dir = TempDir::new().unwrap();
+        let original = dir.child("main.cpp");
+        original
+            .write_str("#include \"local.h\"\n// baca:strip-begin\n#include <map>\n// baca:strip-end\nint main() {}\n")
+            .unwrap();
+        let submitted = dir.child("staged.cpp");
+        submitted
+            .write_str("#include <vector>\nint helper();\nint main() {}\n")
+            .unwrap();
+        let rules = LintRules {
+            forbidden_includes: vec!["vector".to_string(), "map".to_string()],
+            ..Default::default()
+        };
+
+        let violations = lint_file(
+            original.path(),
+            submitted.path(),
+            "main.cpp",
+            &Language::Cpp,
+            &rules,
+        )
+        .unwrap();
+
+        assert_eq!(
+            violations,
+            vec![Violation {
+                file: submitted.path().to_string_lossy().to_string(),
+                line: Some(1),
+                message: "forbidden include 'vector'".to_string(),
+            }]
+        );
+    }
+}
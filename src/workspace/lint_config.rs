@@ -0,0 +1,124 @@
+use crate::error::{Error, Result};
+use crate::workspace::{ConfigObject, Workspace};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Constructs forbidden in submitted files.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LintRules {
+    /// Headers for C/C++, e.g. `vector`, or packages and classes for Java, e.g. `java.util`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub forbidden_includes: Vec<String>,
+    /// Identifiers and keywords, e.g. `goto` or `std::sort`, ignored in comments and strings.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub forbidden_identifiers: Vec<String>,
+    /// Regular expressions matched against every line.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub forbidden_patterns: Vec<String>,
+    /// Maximum size of a single file in bytes.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_size: Option<u64>,
+}
+
+impl LintRules {
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Adds `other` rules, its size limit takes precedence.
+    fn extend(&mut self, other: &LintRules) {
+        self.forbidden_includes
+            .extend(other.forbidden_includes.iter().cloned());
+        self.forbidden_identifiers
+            .extend(other.forbidden_identifiers.iter().cloned());
+        self.forbidden_patterns
+            .extend(other.forbidden_patterns.iter().cloned());
+        self.max_size = other.max_size.or(self.max_size);
+    }
+}
+
+/// Lint rules applied to every task, extended by the rules of a given task.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LintConfig {
+    #[serde(default, skip_serializing_if = "LintRules::is_empty")]
+    pub default: LintRules,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub tasks: BTreeMap<String, LintRules>,
+}
+
+impl LintConfig {
+    pub fn rules_for(&self, task_id: &str) -> LintRules {
+        let mut rules = self.default.clone();
+
+        if let Some(task_rules) = self.tasks.get(task_id) {
+            rules.extend(task_rules);
+        }
+
+        rules
+    }
+}
+
+impl ConfigObject for LintConfig {
+    fn save_config<W: Workspace>(&self, workspace: &W) -> Result<()> {
+        workspace.save_config_object(self)
+    }
+
+    fn read_config<W: Workspace>(workspace: &W) -> Result<Self> {
+        match workspace.read_config_object::<Self>() {
+            Err(Error::ReadingConfig(_)) => Ok(Self::default()),
+            result => result,
+        }
+    }
+
+    fn remove_config<W: Workspace>(workspace: &W) -> Result<()> {
+        workspace.remove_config_object::<Self>()
+    }
+
+    fn config_filename() -> String {
+        "lint".to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::workspace::workspace_dir::tests::make_temp_workspace;
+    use assert_fs::fixture::ChildPath;
+    use assert_fs::prelude::*;
+
+    #[test]
+    fn missing_config_should_have_no_rules() {
+        let (temp_dir, _, workspace) = make_temp_workspace().unwrap();
+        workspace.initialize().unwrap();
+
+        let config = LintConfig::read_config(&workspace).unwrap();
+
+        assert!(config.rules_for("1").is_empty());
+        temp_dir.close().unwrap();
+    }
+
+    #[test]
+    fn task_rules_should_extend_default_ones() {
+        let (temp_dir, mock_paths, workspace) = make_temp_workspace().unwrap();
+        workspace.initialize().unwrap();
+        ChildPath::new(mock_paths.config_path::<LintConfig>())
+            .write_str(
+                "default:\n  forbidden_identifiers: [goto]\n  max_size: 1000\ntasks:\n  \"3\":\n    forbidden_includes: [vector]\n    forbidden_identifiers: [std::sort]\n    max_size: 500\n",
+            )
+            .unwrap();
+
+        let config = LintConfig::read_config(&workspace).unwrap();
+
+        assert_eq!(
+            config.rules_for("3"),
+            LintRules {
+                forbidden_includes: vec!["vector".to_string()],
+                forbidden_identifiers: vec!["goto".to_string(), "std::sort".to_string()],
+                forbidden_patterns: Vec::new(),
+                max_size: Some(500),
+            }
+        );
+        assert_eq!(config.rules_for("4"), config.default);
+        temp_dir.close().unwrap();
+    }
+}
@@ -0,0 +1,134 @@
+use crate::error::{Error, Result};
+use crate::workspace::{ConfigObject, Workspace, WorkspacePaths};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::{env, fs, io};
+use tracing::{debug, info};
+
+static NEXT_ID: AtomicUsize = AtomicUsize::new(0);
+
+/// Workspace kept in memory for a single run, used when there is no `.baca` directory
+/// and the connection is given by `BACA_*` variables or options.
+///
+/// Files which must exist on disk, like submit snapshots, go to a temporary directory
+/// removed together with the workspace.
+pub struct MemoryWorkspace {
+    paths: WorkspacePaths,
+    configs: RefCell<HashMap<String, String>>,
+}
+
+impl MemoryWorkspace {
+    pub fn new() -> Self {
+        let root = env::temp_dir().join(format!(
+            "baca-{}-{}",
+            std::process::id(),
+            NEXT_ID.fetch_add(1, Ordering::Relaxed)
+        ));
+        info!(
+            "Using an in-memory workspace, temporary files in {:?}.",
+            root
+        );
+
+        Self {
+            paths: WorkspacePaths::with_root(&root),
+            configs: RefCell::new(HashMap::new()),
+        }
+    }
+}
+
+impl Drop for MemoryWorkspace {
+    fn drop(&mut self) {
+        let root = self.paths.root();
+
+        if root.exists() {
+            debug!("Removing {:?}.", root);
+            let _ = fs::remove_dir_all(root);
+        }
+    }
+}
+
+impl Workspace for MemoryWorkspace {
+    fn initialize(&self) -> Result<()> {
+        Ok(())
+    }
+
+    fn check_if_initialized(&self) -> Result<()> {
+        Ok(())
+    }
+
+    fn remove_workspace(&self) -> Result<()> {
+        self.configs.borrow_mut().clear();
+        Ok(())
+    }
+
+    fn save_config_object<T>(&self, object: &T) -> Result<()>
+    where
+        T: ConfigObject + 'static,
+    {
+        debug!("Saving object {:?}", &object);
+        let serialized = serde_yaml::to_string(object)?;
+        self.configs
+            .borrow_mut()
+            .insert(T::config_filename(), serialized);
+        Ok(())
+    }
+
+    fn read_config_object<T>(&self) -> Result<T>
+    where
+        T: ConfigObject + 'static,
+    {
+        let configs = self.configs.borrow();
+        let serialized = configs.get(&T::config_filename()).ok_or_else(|| {
+            let e = io::Error::new(io::ErrorKind::NotFound, T::config_filename());
+            Error::ReadingConfig(e.into())
+        })?;
+
+        Ok(serde_yaml::from_str::<T>(serialized)?)
+    }
+
+    fn remove_config_object<T>(&self) -> Result<()>
+    where
+        T: ConfigObject + 'static,
+    {
+        self.configs.borrow_mut().remove(&T::config_filename());
+        Ok(())
+    }
+
+    fn get_paths(&self) -> WorkspacePaths {
+        self.paths.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::workspace::workspace_dir::tests::make_baca;
+    use crate::workspace::{ConnectionConfig, Journal, UserConfig};
+
+    #[test]
+    fn configs_should_be_kept_in_memory() {
+        let workspace = MemoryWorkspace::new();
+        let root = workspace.get_paths().root().to_path_buf();
+
+        assert!(Journal::read_config(&workspace).unwrap().entries.is_empty());
+        assert!(UserConfig::read_config(&workspace)
+            .unwrap()
+            .profiles
+            .is_empty());
+        assert!(matches!(
+            ConnectionConfig::read_config(&workspace),
+            Err(Error::WorkspaceCorrupted)
+        ));
+
+        make_baca().save_config(&workspace).unwrap();
+        fs::create_dir_all(workspace.get_paths().submissions_dir()).unwrap();
+
+        assert_eq!(
+            ConnectionConfig::read_config(&workspace).unwrap(),
+            make_baca()
+        );
+        drop(workspace);
+        assert!(!root.exists());
+    }
+}
@@ -2,23 +2,49 @@ use crate::error::Result;
 #[cfg(test)]
 use mockall::{automock, predicate::*};
 
+pub use self::add_header::{add_header, render_header, HeaderFields};
 pub use self::config_object::ConfigObject;
-pub use self::connection_config::ConnectionConfig;
+pub use self::connection_config::{ConnectionConfig, ConnectionOverrides};
+pub use self::credentials::CredentialProvider;
+pub use self::inline_includes::inline_includes;
+pub use self::journal::{Journal, JournalEntry};
+pub use self::lint_config::{LintConfig, LintRules};
+pub use self::memory_workspace::MemoryWorkspace;
 pub use self::no_main::remove_main;
 pub use self::no_polish::make_polishless_file;
-pub use self::submit_config::SubmitConfig;
+pub use self::normalize::normalize;
+pub use self::secret_store::{Encryption, SecretKey};
+pub use self::source_files::{select_sources, SourceFile};
+pub use self::staging_dir::StagingDir;
+pub use self::strip_debug::strip_debug;
+pub use self::submit_config::{HeaderConfig, NormalizeConfig, SubmitConfig};
+pub use self::user_config::{Profile, UserConfig};
 pub use self::workspace_dir::WorkspaceDir;
 pub use self::workspace_paths::WorkspacePaths;
-pub use self::zip::zip_file;
+pub use self::zip::zip_files;
 
+mod add_header;
 pub mod baca_release;
 pub mod config_editor;
 pub mod config_object;
 mod connection_config;
+mod credentials;
 pub mod header_check;
+mod inline_includes;
+pub mod journal;
+pub mod lexer;
+pub mod lint;
+mod lint_config;
+mod memory_workspace;
 mod no_main;
 mod no_polish;
+mod normalize;
+pub mod secret_store;
+mod source_files;
+mod staging_dir;
+mod strip_debug;
 mod submit_config;
+mod user_config;
 pub mod workspace_dir;
 pub mod workspace_paths;
 mod zip;
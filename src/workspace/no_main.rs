@@ -1,23 +1,28 @@
 use crate::error::*;
-use regex::Regex;
+use crate::model::Language;
+use crate::workspace::lexer::{split_segments, Segment};
 use std::fs;
 use std::fs::File;
 use std::io::Write;
 use std::path::{Path, PathBuf};
 use tracing::{debug, info};
 
-pub fn remove_main<P>(input_file: P) -> Result<PathBuf>
+/// Removes the `main` function from a C, C++ or Java file.
+/// Fails with `Error::MainNotFound`, instead of leaving the file unchanged.
+pub fn remove_main<P, Q>(input_file: P, language: &Language, output_dir: Q) -> Result<PathBuf>
 where
     P: AsRef<Path>,
+    Q: AsRef<Path>,
 {
     let input_file: &Path = input_file.as_ref();
     info!("Removing main from {:?}", input_file);
 
     let content = fs::read_to_string(input_file)?;
-    let content = strip_main(&content);
+    let content = strip_main(&content, language)?;
 
-    let filepath =
-        std::env::temp_dir().join(input_file.file_name().ok_or(Error::InputFileDoesNotExist)?);
+    let filepath = output_dir
+        .as_ref()
+        .join(input_file.file_name().ok_or(Error::InputFileDoesNotExist)?);
     let mut file = File::create(filepath.clone())?;
     file.write_all(content.as_ref())?;
 
@@ -27,39 +32,237 @@ where
     Ok(filepath)
 }
 
-fn strip_main(content: &str) -> String {
-    let re = Regex::new(r#"int\s+main"#).unwrap();
-    let f = re.find(content);
+fn strip_main(content: &str, language: &Language) -> Result<String> {
+    // Java's main is a method, so it lives inside a class body.
+    let main_depth = match language {
+        Language::Cpp | Language::CppWithFileSupport => 0,
+        Language::Java => 1,
+        _ => return Err(Error::UnsupportedLanguage(language.to_string())),
+    };
 
-    if f.is_none() {
-        return content.to_string();
+    let tokens = tokenize(content, language);
+    let (start, end) = find_main(&tokens, main_depth).ok_or(Error::MainNotFound)?;
+
+    let mut result = content.to_string();
+    result.replace_range(start..end, "");
+    Ok(result)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum TokenKind {
+    Ident(String),
+    Punct(char),
+    /// Preprocessor directive, it breaks declarations just like `;` does.
+    Directive,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Token {
+    kind: TokenKind,
+    start: usize,
+    end: usize,
+}
+
+impl Token {
+    fn is_punct(&self, c: char) -> bool {
+        self.kind == TokenKind::Punct(c)
     }
-    let f = f.unwrap();
 
-    let mut brackets = 0;
-    let mut end = 0;
-    let mut i = f.end();
-    let mut first_bracket = false;
+    fn is_ident(&self, name: &str) -> bool {
+        matches!(&self.kind, TokenKind::Ident(x) if x == name)
+    }
 
-    for c in content.chars().skip(f.end()) {
-        i += 1;
+    /// Tokens separating declarations.
+    fn is_boundary(&self) -> bool {
+        self.is_punct(';')
+            || self.is_punct('{')
+            || self.is_punct('}')
+            || self.kind == TokenKind::Directive
+    }
+}
 
-        if c == '{' {
-            brackets += 1;
-            first_bracket = true;
-        } else if c == '}' {
-            brackets -= 1;
+/// Splits the code segments into identifiers and punctuation, skipping whitespace, numbers
+/// and the comments and literals found by [`split_segments`].
+fn tokenize(content: &str, language: &Language) -> Vec<Token> {
+    let java = *language == Language::Java;
+    let bytes = content.as_bytes();
+    let mut tokens = Vec::new();
+    let mut line_start = true;
+    // End of the last directive, which may span comments and literals.
+    let mut skip_to = 0;
+
+    for (segment, range) in split_segments(content, language) {
+        if segment != Segment::Code {
+            line_start &= range.end <= skip_to;
+            continue;
         }
 
-        if first_bracket && brackets == 0 {
-            end = i;
-            break;
+        let end = range.end;
+        let mut i = range.start.max(skip_to);
+
+        while i < end {
+            let c = bytes[i];
+
+            if c == b'\n' {
+                line_start = true;
+                i += 1;
+                continue;
+            }
+
+            if c.is_ascii_whitespace() {
+                i += 1;
+                continue;
+            }
+
+            let at_line_start = line_start;
+            line_start = false;
+
+            if c == b'#' && at_line_start && !java {
+                skip_to = skip_directive(content, i);
+                tokens.push(Token {
+                    kind: TokenKind::Directive,
+                    start: i,
+                    end: skip_to,
+                });
+                i = skip_to;
+            } else if c.is_ascii_digit() {
+                // Also consumes C++14 digit separators, like 1'000'000.
+                i += 1;
+                while i < end && (bytes[i].is_ascii_alphanumeric() || b"_.'".contains(&bytes[i])) {
+                    i += 1;
+                }
+            } else if c.is_ascii_alphabetic() || c == b'_' || c == b'$' || c >= 0x80 {
+                let start = i;
+                while i < end
+                    && (bytes[i].is_ascii_alphanumeric()
+                        || bytes[i] == b'_'
+                        || bytes[i] == b'$'
+                        || bytes[i] >= 0x80)
+                {
+                    i += 1;
+                }
+
+                tokens.push(Token {
+                    kind: TokenKind::Ident(content[start..i].to_string()),
+                    start,
+                    end: i,
+                });
+            } else {
+                tokens.push(Token {
+                    kind: TokenKind::Punct(c as char),
+                    start: i,
+                    end: i + 1,
+                });
+                i += 1;
+            }
         }
     }
 
-    let mut result = content.to_string();
-    result.replace_range(f.start()..end, "");
-    result
+    tokens
+}
+
+fn find_from(content: &str, from: usize, pattern: &str) -> Option<usize> {
+    content[from..].find(pattern).map(|x| x + from)
+}
+
+fn skip_directive(content: &str, start: usize) -> usize {
+    let mut i = start;
+
+    loop {
+        let end = find_from(content, i, "\n").unwrap_or(content.len());
+
+        if !content[i..end].trim_end().ends_with('\\') || end == content.len() {
+            return end;
+        }
+
+        i = end + 1;
+    }
+}
+
+fn matching(tokens: &[Token], open_index: usize, open: char, close: char) -> Option<usize> {
+    let mut depth = 0;
+
+    for (i, token) in tokens.iter().enumerate().skip(open_index) {
+        if token.is_punct(open) {
+            depth += 1;
+        } else if token.is_punct(close) {
+            depth -= 1;
+
+            if depth == 0 {
+                return Some(i);
+            }
+        }
+    }
+
+    None
+}
+
+/// Returns byte range of the `main` definition, from its return type to the closing brace.
+fn find_main(tokens: &[Token], main_depth: usize) -> Option<(usize, usize)> {
+    let mut depth: usize = 0;
+
+    for (i, token) in tokens.iter().enumerate() {
+        if token.is_punct('{') {
+            depth += 1;
+            continue;
+        }
+
+        if token.is_punct('}') {
+            depth = depth.saturating_sub(1);
+            continue;
+        }
+
+        if depth != main_depth || !token.is_ident("main") {
+            continue;
+        }
+
+        if !tokens.get(i + 1).is_some_and(|x| x.is_punct('(')) {
+            continue;
+        }
+
+        let is_declaration = match i.checked_sub(1).map(|x| &tokens[x]) {
+            None => true,
+            Some(previous) => {
+                previous.is_boundary()
+                    || matches!(previous.kind, TokenKind::Ident(_))
+                    || previous.is_punct('*')
+                    || previous.is_punct('&')
+                    || previous.is_punct('>')
+            }
+        };
+
+        if !is_declaration {
+            continue;
+        }
+
+        let close_paren = matching(tokens, i + 1, '(', ')')?;
+
+        // Skips trailing return types, `throws` clauses and K&R parameter declarations.
+        let body_index = tokens
+            .iter()
+            .enumerate()
+            .skip(close_paren + 1)
+            .find(|(_, x)| x.is_punct('{') || x.is_punct('}') || x.kind == TokenKind::Directive)
+            .filter(|(_, x)| x.is_punct('{'))
+            .map(|(index, _)| index);
+
+        // A prototype like `int main();`
+        let body_index = match body_index {
+            Some(index) if !tokens[close_paren + 1].is_punct(';') => index,
+            _ => continue,
+        };
+
+        let body_end = matching(tokens, body_index, '{', '}')?;
+
+        let first = tokens[..i]
+            .iter()
+            .rposition(|x| x.is_boundary())
+            .map_or(0, |x| x + 1);
+
+        return Some((tokens[first].start, tokens[body_end].end));
+    }
+
+    None
 }
 
 #[cfg(test)]
@@ -79,12 +282,13 @@ mod tests {
             return;
         }
 
-        void main() {
+        int mainly() {
+            return Add(nullptr, nullptr), 0; // main() is elsewhere
         }
         "#;
 
-        let actual = strip_main(input);
-        assert_eq!(actual, input);
+        let actual = strip_main(input, &Language::Cpp);
+        assert!(matches!(actual, Err(Error::MainNotFound)), "{:?}", actual);
     }
 
     #[test]
@@ -92,7 +296,7 @@ mod tests {
         let input = r#"int main() {
 }"#;
         let expected = r#""#;
-        let actual = strip_main(input);
+        let actual = strip_main(input, &Language::Cpp).unwrap();
 
         assert_eq!(actual, expected);
     }
@@ -101,7 +305,7 @@ mod tests {
     fn one_line() {
         let input = r#"int main() {} int foo() { return 5; }"#;
         let expected = r#" int foo() { return 5; }"#;
-        let actual = strip_main(input);
+        let actual = strip_main(input, &Language::Cpp).unwrap();
 
         assert_eq!(actual, expected);
     }
@@ -140,7 +344,7 @@ void Add(int** arr, int*[] arr2)
 
         "#;
 
-        let actual = strip_main(input);
+        let actual = strip_main(input, &Language::Cpp).unwrap();
         assert_eq!(actual, expected);
     }
 
@@ -176,7 +380,7 @@ void Add(int** arr, int*[] arr2)
 
         "#;
 
-        let actual = strip_main(input);
+        let actual = strip_main(input, &Language::Cpp).unwrap();
         assert_eq!(actual, expected);
     }
 
@@ -222,7 +426,7 @@ int moin(int argc, char** argv)
 }
         "#;
 
-        let actual = strip_main(input);
+        let actual = strip_main(input, &Language::Cpp).unwrap();
         assert_eq!(actual, expected);
     }
 
@@ -273,7 +477,7 @@ int moin(int argc, char** argv)
 }
         "#;
 
-        let actual = strip_main(input);
+        let actual = strip_main(input, &Language::Cpp).unwrap();
         assert_eq!(actual, expected);
     }
 
@@ -337,7 +541,7 @@ int moin(int argc, char** argv)
 }
         "#;
 
-        let actual = strip_main(input);
+        let actual = strip_main(input, &Language::Cpp).unwrap();
         assert_eq!(actual, expected);
     }
 
@@ -386,11 +590,14 @@ int moin(int argc, char** argv)
     return 5;
 }
         "#;
-        let original_filepath = std::env::temp_dir().join("input.cpp");
+        let input_dir = assert_fs::TempDir::new().unwrap();
+        let output_dir = assert_fs::TempDir::new().unwrap();
+        let original_filepath = input_dir.join("input.cpp");
         let mut original_file = File::create(original_filepath.clone()).unwrap();
         original_file.write_all(input.as_ref()).unwrap();
 
-        let actual_filepath = remove_main(&original_filepath).unwrap();
+        let actual_filepath =
+            remove_main(&original_filepath, &Language::Cpp, output_dir.path()).unwrap();
 
         assert!(predicate::path::exists().eval(&actual_filepath));
         assert!(predicate::path::eq_file(&actual_filepath)
@@ -398,5 +605,120 @@ int moin(int argc, char** argv)
             .unwrap()
             .eval(expected));
         assert_eq!(actual_filepath.file_name().unwrap(), "input.cpp");
+        assert!(actual_filepath.starts_with(output_dir.path()));
+        assert!(predicate::path::eq_file(&original_filepath)
+            .utf8()
+            .unwrap()
+            .eval(input));
+    }
+
+    fn assert_stripped(input: &str, expected: &str, language: &Language) {
+        assert_eq!(strip_main(input, language).unwrap(), expected);
+    }
+
+    #[test]
+    fn main_in_comments_and_strings_should_be_ignored() {
+        let input = r#"// int main() { old }
+/* int main() { } */
+const char* s = "int main() {";
+int main() { puts("}"); return 0; }
+"#;
+        let expected = r#"// int main() { old }
+/* int main() { } */
+const char* s = "int main() {";
+
+"#;
+        assert_stripped(input, expected, &Language::Cpp);
+    }
+
+    #[test]
+    fn braces_in_char_and_raw_string_literals_should_be_ignored() {
+        let input = r#"int f() { return 1'000; }
+int main() {
+    char open = '{';
+    auto s = R"x(}})x";
+    return 0;
+}
+"#;
+        assert_stripped(input, "int f() { return 1'000; }\n\n", &Language::Cpp);
+    }
+
+    #[test]
+    fn trailing_return_type_and_signed_main_should_be_removed() {
+        assert_stripped(
+            "#include <cstdio>\nauto main() -> int { return 0; }\n",
+            "#include <cstdio>\n\n",
+            &Language::Cpp,
+        );
+        assert_stripped(
+            "int x;\nsigned main() { return 0; }\n",
+            "int x;\n\n",
+            &Language::Cpp,
+        );
+    }
+
+    #[test]
+    fn k_and_r_main_should_be_removed() {
+        let input = "int f();\nmain(argc, argv)\nint argc;\nchar **argv;\n{\n    return f();\n}\n";
+
+        assert_stripped(input, "int f();\n\n", &Language::Cpp);
+    }
+
+    #[test]
+    fn main_prototype_and_calls_should_not_be_removed() {
+        let input = "int main();\nint f() { return main(); }\nint main() { return f(); }\n";
+
+        assert_stripped(
+            input,
+            "int main();\nint f() { return main(); }\n\n",
+            &Language::Cpp,
+        );
+    }
+
+    #[test]
+    fn non_ascii_content_should_be_handled() {
+        assert_stripped(
+            "// Zażółć gęślą jaźń\nint main() { return 0; }\n// koniec\n",
+            "// Zażółć gęślą jaźń\n\n// koniec\n",
+            &Language::Cpp,
+        );
+    }
+
+    #[test]
+    fn java_main_should_be_removed() {
+        let input = r#"// Hubert Jaremko
+public class Source {
+    public static int add(int a, int b) { return a + b; }
+
+    @SuppressWarnings("unused")
+    public static void main(String[] args) throws Exception {
+        System.out.println("{" + add(1, 2));
+    }
+}
+"#;
+        let expected = r#"// Hubert Jaremko
+public class Source {
+    public static int add(int a, int b) { return a + b; }
+
+    
+}
+"#;
+        assert_stripped(input, expected, &Language::Java);
+    }
+
+    #[test]
+    fn java_main_calls_should_not_be_removed() {
+        let input = "class A {\n    void f() { B.main(null); }\n}\n";
+
+        let actual = strip_main(input, &Language::Java);
+
+        assert!(matches!(actual, Err(Error::MainNotFound)), "{:?}", actual);
+    }
+
+    #[test]
+    fn unsupported_language_should_fail() {
+        let actual = strip_main("main() {}", &Language::Bash);
+
+        assert!(matches!(actual, Err(Error::UnsupportedLanguage(_))));
     }
 }
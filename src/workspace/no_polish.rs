@@ -6,9 +6,10 @@ use std::io::Write;
 use std::path::{Path, PathBuf};
 use tracing::{debug, info};
 
-pub fn make_polishless_file<P>(input_file: P) -> Result<PathBuf>
+pub fn make_polishless_file<P, Q>(input_file: P, output_dir: Q) -> Result<PathBuf>
 where
     P: AsRef<Path>,
+    Q: AsRef<Path>,
 {
     let input_file: &Path = input_file.as_ref();
     info!("Removing Polish diacritics from {:?}", input_file);
@@ -16,8 +17,9 @@ where
     let content = fs::read_to_string(input_file)?;
     let content = deunicode(&content);
 
-    let filepath =
-        std::env::temp_dir().join(input_file.file_name().ok_or(Error::InputFileDoesNotExist)?);
+    let filepath = output_dir
+        .as_ref()
+        .join(input_file.file_name().ok_or(Error::InputFileDoesNotExist)?);
     let mut file = File::create(filepath.clone())?;
     file.write_all(content.as_ref())?;
 
@@ -45,7 +47,8 @@ mod tests {
         let expected = "azzcloc";
 
         let input_file = make_input_file(input, "all");
-        let actual_filepath = make_polishless_file(input_file).unwrap();
+        let output_dir = assert_fs::TempDir::new().unwrap();
+        let actual_filepath = make_polishless_file(input_file, output_dir.path()).unwrap();
 
         assert!(predicate::path::exists().eval(&actual_filepath));
         assert!(predicate::path::eq_file(&actual_filepath)
@@ -60,7 +63,8 @@ mod tests {
         let expected = "  azzasdghjkescclosda  3423c   ";
 
         let input_file = make_input_file(input, "mixed");
-        let actual_filepath = make_polishless_file(input_file).unwrap();
+        let output_dir = assert_fs::TempDir::new().unwrap();
+        let actual_filepath = make_polishless_file(input_file, output_dir.path()).unwrap();
 
         assert!(predicate::path::exists().eval(&actual_filepath));
         assert!(predicate::path::eq_file(&actual_filepath)
@@ -75,7 +79,8 @@ mod tests {
         let expected = "  axxasdghjkescclosda  3423c  \n ";
 
         let input_file = make_input_file(input, "no");
-        let actual_filepath = make_polishless_file(input_file).unwrap();
+        let output_dir = assert_fs::TempDir::new().unwrap();
+        let actual_filepath = make_polishless_file(input_file, output_dir.path()).unwrap();
 
         assert!(predicate::path::exists().eval(&actual_filepath));
         assert!(predicate::path::eq_file(&actual_filepath)
@@ -0,0 +1,316 @@
+use crate::error::*;
+use crate::model::Language;
+use crate::workspace::lexer::{split_segments, Segment};
+use crate::workspace::NormalizeConfig;
+use deunicode::deunicode;
+use encoding_rs::Encoding;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::fs::File;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use tracing::{debug, info};
+
+const BOM: char = '\u{feff}';
+
+/// Parts of the file, where non-ASCII characters are transliterated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Transliterate {
+    Comments,
+    OutsideStrings,
+}
+
+impl FromStr for Transliterate {
+    type Err = Error;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "comments" => Ok(Transliterate::Comments),
+            "outside-strings" => Ok(Transliterate::OutsideStrings),
+            _ => Err(Error::InvalidArgument),
+        }
+    }
+}
+
+/// Decodes `input_file` as UTF-8 or the declared encoding and applies the enabled normalizations,
+/// printing what was changed.
+pub fn normalize<P, Q>(
+    input_file: P,
+    language: &Language,
+    config: &NormalizeConfig,
+    output_dir: Q,
+) -> Result<PathBuf>
+where
+    P: AsRef<Path>,
+    Q: AsRef<Path>,
+{
+    let input_file: &Path = input_file.as_ref();
+    info!("Normalizing {:?}", input_file);
+
+    let file_name = input_file.file_name().ok_or(Error::InputFileDoesNotExist)?;
+    let bytes = fs::read(input_file)?;
+    let (content, changes) = normalize_content(&bytes, language, config).map_err(|e| match e {
+        Error::InvalidEncoding(_, encoding) => {
+            Error::InvalidEncoding(file_name.to_string_lossy().to_string(), encoding)
+        }
+        e => e,
+    })?;
+
+    if changes.is_empty() {
+        info!("Nothing to normalize");
+        return Ok(input_file.to_path_buf());
+    }
+
+    println!(
+        "Normalized {}: {}.",
+        file_name.to_string_lossy(),
+        changes.join(", ")
+    );
+
+    let filepath = output_dir.as_ref().join(file_name);
+    let mut file = File::create(filepath.clone())?;
+    file.write_all(content.as_ref())?;
+
+    debug!("New input file path: {:?}", filepath);
+    debug!("New input file content:\n{}", content);
+
+    Ok(filepath)
+}
+
+/// Returns the normalized content along with the descriptions of the changes made.
+fn normalize_content(
+    bytes: &[u8],
+    language: &Language,
+    config: &NormalizeConfig,
+) -> Result<(String, Vec<String>)> {
+    let mut changes = Vec::new();
+
+    let mut content = match &config.encoding {
+        Some(label) => {
+            let encoding = Encoding::for_label(label.trim().as_bytes())
+                .ok_or_else(|| Error::UnknownEncoding(label.clone()))?;
+            let decoded = encoding
+                .decode_without_bom_handling_and_without_replacement(bytes)
+                .ok_or_else(|| Error::InvalidEncoding(String::new(), encoding.name().into()))?;
+
+            if encoding != encoding_rs::UTF_8 {
+                changes.push(format!("transcoded from {}", encoding.name()));
+            }
+            decoded.into_owned()
+        }
+        None => String::from_utf8(bytes.to_vec())
+            .map_err(|_| Error::InvalidEncoding(String::new(), "UTF-8".to_string()))?,
+    };
+
+    if config.strip_bom && content.starts_with(BOM) {
+        content.remove(0);
+        changes.push("removed BOM".to_string());
+    }
+
+    if config.crlf_to_lf {
+        let count = content.matches("\r\n").count();
+
+        if count > 0 {
+            content = content.replace("\r\n", "\n");
+            changes.push(format!("converted {} CRLF line endings", count));
+        }
+    }
+
+    if let Some(scope) = config.transliterate {
+        let (transliterated, count) = transliterate(&content, language, scope);
+
+        if count > 0 {
+            content = transliterated;
+            changes.push(match scope {
+                Transliterate::Comments => {
+                    format!("transliterated {} characters in comments", count)
+                }
+                Transliterate::OutsideStrings => {
+                    format!("transliterated {} characters outside strings", count)
+                }
+            });
+        }
+    }
+
+    Ok((content, changes))
+}
+
+/// Returns the content with transliterated `scope` and the number of replaced characters.
+fn transliterate(content: &str, language: &Language, scope: Transliterate) -> (String, usize) {
+    let mut result = String::with_capacity(content.len());
+    let mut count = 0;
+
+    for (segment, range) in split_segments(content, language) {
+        let text = &content[range];
+        let selected = match scope {
+            Transliterate::Comments => segment == Segment::Comment,
+            Transliterate::OutsideStrings => segment != Segment::Literal,
+        };
+
+        if selected && !text.is_ascii() {
+            count += text.chars().filter(|x| !x.is_ascii()).count();
+            result.push_str(&deunicode(text));
+        } else {
+            result.push_str(text);
+        }
+    }
+
+    (result, count)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert_fs::prelude::*;
+    use assert_fs::TempDir;
+
+    fn make_config() -> NormalizeConfig {
+        NormalizeConfig {
+            strip_bom: true,
+            crlf_to_lf: true,
+            ..Default::default()
+        }
+    }
+
+    fn normalize_str(bytes: &[u8], config: &NormalizeConfig) -> (String, Vec<String>) {
+        normalize_content(bytes, &Language::Cpp, config).unwrap()
+    }
+
+    #[test]
+    fn bom_and_crlf_should_be_removed() {
+        let (content, changes) = normalize_str(
+            "\u{feff}// Hubert Jaremko\r\nint main() {}\r\n".as_bytes(),
+            &make_config(),
+        );
+
+        assert_eq!(content, "// Hubert Jaremko\nint main() {}\n");
+        assert_eq!(
+            changes,
+            vec!["removed BOM", "converted 2 CRLF line endings"]
+        );
+    }
+
+    #[test]
+    fn disabled_options_should_keep_content() {
+        let input = "\u{feff}// Hubert Jaremko\r\n";
+
+        let (content, changes) = normalize_str(input.as_bytes(), &NormalizeConfig::default());
+
+        assert_eq!(content, input);
+        assert!(changes.is_empty());
+    }
+
+    #[test]
+    fn declared_encoding_should_be_transcoded() {
+        let mut config = make_config();
+        config.encoding = Some("windows-1250".to_string());
+        // "// Zażółć" in CP1250.
+        let input = b"// Za\xbf\xf3\xb3\xe6\n";
+
+        let (content, changes) = normalize_str(input, &config);
+
+        assert_eq!(content, "// Zażółć\n");
+        assert_eq!(changes, vec!["transcoded from windows-1250"]);
+    }
+
+    #[test]
+    fn invalid_utf8_should_fail() {
+        let result = normalize_content(b"// Za\xbf\n", &Language::Cpp, &make_config());
+
+        assert!(
+            matches!(&result, Err(Error::InvalidEncoding(_, encoding)) if encoding == "UTF-8"),
+            "{:?}",
+            result
+        );
+    }
+
+    #[test]
+    fn unknown_encoding_should_fail() {
+        let mut config = make_config();
+        config.encoding = Some("klingon".to_string());
+
+        let result = normalize_content(b"", &Language::Cpp, &config);
+
+        assert!(
+            matches!(result, Err(Error::UnknownEncoding(_))),
+            "{:?}",
+            result
+        );
+    }
+
+    #[test]
+    fn only_comments_should_be_transliterated() {
+        let input = "// Zażółć\nauto s = \"gęślą\"; /* jaźń */ char c = 'ó';\n";
+
+        let (content, count) = transliterate(input, &Language::Cpp, Transliterate::Comments);
+
+        assert_eq!(
+            content,
+            "// Zazolc\nauto s = \"gęślą\"; /* jazn */ char c = 'ó';\n"
+        );
+        assert_eq!(count, 6);
+    }
+
+    #[test]
+    fn strings_should_not_be_transliterated() {
+        let input = "int żółw = 1'000; auto s = u8R\"x(\"ł\")x\"; // ą\n";
+
+        let (content, count) = transliterate(input, &Language::Cpp, Transliterate::OutsideStrings);
+
+        assert_eq!(
+            content,
+            "int zolw = 1'000; auto s = u8R\"x(\"ł\")x\"; // a\n"
+        );
+        assert_eq!(count, 4);
+    }
+
+    #[test]
+    fn java_text_blocks_should_not_be_transliterated() {
+        let input = "String s = \"\"\"\n  \"ł\"\n  \"\"\"; // ą\n";
+
+        let (content, _) = transliterate(input, &Language::Java, Transliterate::OutsideStrings);
+
+        assert_eq!(content, "String s = \"\"\"\n  \"ł\"\n  \"\"\"; // a\n");
+    }
+
+    #[test]
+    fn bash_comments_should_start_a_word() {
+        let input = "echo ${#ąrr} 'ł' # ż\n";
+
+        let (content, _) = transliterate(input, &Language::Bash, Transliterate::Comments);
+
+        assert_eq!(content, "echo ${#ąrr} 'ł' # z\n");
+    }
+
+    #[test]
+    fn normalized_file_should_be_saved() {
+        let dir = TempDir::new().unwrap();
+        let output_dir = TempDir::new().unwrap();
+        let input = dir.child("main.cpp");
+        input.write_str("int main() {}\r\n").unwrap();
+
+        let actual = normalize(
+            input.path(),
+            &Language::Cpp,
+            &make_config(),
+            output_dir.path(),
+        )
+        .unwrap();
+
+        assert_eq!(actual, output_dir.join("main.cpp"));
+        assert_eq!(fs::read_to_string(actual).unwrap(), "int main() {}\n");
+    }
+
+    #[test]
+    fn unchanged_file_should_not_be_copied() {
+        let dir = TempDir::new().unwrap();
+        let input = dir.child("main.cpp");
+        input.write_str("int main() {}\n").unwrap();
+
+        let actual = normalize(input.path(), &Language::Cpp, &make_config(), dir.path()).unwrap();
+
+        assert_eq!(actual, input.path());
+    }
+}
@@ -0,0 +1,320 @@
+use crate::error::{Error, Result};
+use crate::interactive;
+use crate::workspace::{Workspace, WorkspacePaths};
+use aes_gcm::aead::Aead;
+use aes_gcm::{Aes256Gcm, KeyInit, Nonce};
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use dialoguer::Password;
+use hmac::Hmac;
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use std::env;
+use std::fs;
+use std::path::Path;
+use std::sync::Mutex;
+use time::OffsetDateTime;
+use tracing::{debug, info};
+
+type HmacSha256 = Hmac<Sha256>;
+
+const DEFAULT_ROUNDS: u32 = 200_000;
+const NONCE_LEN: usize = 12;
+const CHECK: &[u8] = b"baca";
+
+/// Key of the current process, so the passphrase is asked for at most once per run.
+static UNLOCKED: Mutex<Option<SecretKey>> = Mutex::new(None);
+
+/// Encryption of the workspace secrets with a key derived from the user passphrase,
+/// enabled for all workspaces when present in the user config.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Encryption {
+    /// PBKDF2 salt, base64 encoded.
+    pub salt: String,
+    pub rounds: u32,
+    /// A known value encrypted with the key, tells a wrong passphrase apart.
+    pub check: String,
+    /// How long the passphrase is remembered, 0 asks for it on every run.
+    #[serde(default = "default_cache_minutes")]
+    pub cache_minutes: u64,
+}
+
+fn default_cache_minutes() -> u64 {
+    15
+}
+
+impl Encryption {
+    pub fn new(passphrase: &str) -> Result<(Self, SecretKey)> {
+        Self::with_rounds(passphrase, DEFAULT_ROUNDS)
+    }
+
+    pub fn with_rounds(passphrase: &str, rounds: u32) -> Result<(Self, SecretKey)> {
+        let salt = BASE64.encode(random_bytes::<16>()?);
+        let key = SecretKey::derive(passphrase, &salt, rounds)?;
+        let encryption = Self {
+            salt,
+            rounds,
+            check: key.encrypt(CHECK)?,
+            cache_minutes: default_cache_minutes(),
+        };
+
+        Ok((encryption, key))
+    }
+
+    /// Derives the key, failing on a wrong passphrase.
+    pub fn unlock(&self, passphrase: &str) -> Result<SecretKey> {
+        let key = SecretKey::derive(passphrase, &self.salt, self.rounds)?;
+
+        match self.verify(&key) {
+            true => Ok(key),
+            false => Err(Error::WrongPassphrase),
+        }
+    }
+
+    fn verify(&self, key: &SecretKey) -> bool {
+        key.decrypt(&self.check).is_ok_and(|x| x == CHECK)
+    }
+}
+
+/// AES-256-GCM key.
+#[derive(Clone)]
+pub struct SecretKey([u8; 32]);
+
+impl SecretKey {
+    fn derive(passphrase: &str, salt: &str, rounds: u32) -> Result<Self> {
+        let salt = BASE64.decode(salt).map_err(|_| Error::WorkspaceCorrupted)?;
+        let mut key = [0; 32];
+        pbkdf2::pbkdf2::<HmacSha256>(passphrase.as_bytes(), &salt, rounds, &mut key);
+        Ok(Self(key))
+    }
+
+    fn from_base64(encoded: &str) -> Option<Self> {
+        BASE64.decode(encoded).ok()?.try_into().ok().map(Self)
+    }
+
+    fn to_base64(&self) -> String {
+        BASE64.encode(self.0)
+    }
+
+    /// Base64 of the random nonce followed by the ciphertext with its authentication tag.
+    pub fn encrypt(&self, plaintext: &[u8]) -> Result<String> {
+        let nonce = random_bytes::<NONCE_LEN>()?;
+        let mut ciphertext = self
+            .cipher()
+            .encrypt(Nonce::from_slice(&nonce), plaintext)
+            .map_err(|_| Error::Other("encryption failed".into()))?;
+
+        let mut blob = nonce.to_vec();
+        blob.append(&mut ciphertext);
+        Ok(BASE64.encode(blob))
+    }
+
+    pub fn decrypt(&self, encoded: &str) -> Result<Vec<u8>> {
+        let blob = BASE64
+            .decode(encoded)
+            .map_err(|_| Error::SecretsCorrupted)?;
+
+        if blob.len() < NONCE_LEN {
+            return Err(Error::SecretsCorrupted);
+        }
+
+        let (nonce, ciphertext) = blob.split_at(NONCE_LEN);
+        self.cipher()
+            .decrypt(Nonce::from_slice(nonce), ciphertext)
+            .map_err(|_| Error::SecretsCorrupted)
+    }
+
+    fn cipher(&self) -> Aes256Gcm {
+        Aes256Gcm::new(&self.0.into())
+    }
+}
+
+/// Key remembered between runs until `expires`, in a file readable only by the user.
+#[derive(Debug, Serialize, Deserialize)]
+struct Session {
+    expires: i64,
+    key: String,
+}
+
+/// Returns the key from this run, the session file, `BACA_PASSPHRASE` or asks for the passphrase.
+pub fn unlock<W: Workspace>(workspace: &W, encryption: &Encryption) -> Result<SecretKey> {
+    let cached = UNLOCKED.lock().unwrap().clone();
+    if let Some(key) = cached.filter(|x| encryption.verify(x)) {
+        return Ok(key);
+    }
+
+    let session_path = workspace.get_paths().session_path();
+    let remembered = session_path.as_deref().and_then(read_session);
+    if let Some(key) = remembered.filter(|x| encryption.verify(x)) {
+        info!("Using passphrase remembered in {:?}.", session_path);
+        *UNLOCKED.lock().unwrap() = Some(key.clone());
+        return Ok(key);
+    }
+
+    let passphrase = match env::var("BACA_PASSPHRASE") {
+        Ok(passphrase) => passphrase,
+        Err(_) => {
+            interactive::require("the passphrase with BACA_PASSPHRASE")?;
+            Password::new().with_prompt("Passphrase").interact()?
+        }
+    };
+    let key = encryption.unlock(&passphrase)?;
+    remember(&workspace.get_paths(), encryption, &key)?;
+    Ok(key)
+}
+
+/// Keeps the key for this run and, unless disabled, for `cache_minutes` in the session file.
+pub fn remember(paths: &WorkspacePaths, encryption: &Encryption, key: &SecretKey) -> Result<()> {
+    *UNLOCKED.lock().unwrap() = Some(key.clone());
+    let session_path = match paths.session_path() {
+        Some(path) => path,
+        None => {
+            debug!("No user config directory, the passphrase is not remembered.");
+            return Ok(());
+        }
+    };
+
+    if encryption.cache_minutes == 0 {
+        if session_path.exists() {
+            fs::remove_file(&session_path)?;
+        }
+        return Ok(());
+    }
+
+    let session = Session {
+        expires: OffsetDateTime::now_utc().unix_timestamp() + 60 * encryption.cache_minutes as i64,
+        key: key.to_base64(),
+    };
+
+    if let Some(dir) = session_path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    // Restricted before writing, so the key is never readable by others.
+    fs::write(&session_path, "")?;
+    restrict_permissions(&session_path)?;
+    fs::write(&session_path, serde_yaml::to_string(&session)?)?;
+    debug!(
+        "Session saved to {:?}, expires {}.",
+        session_path, session.expires
+    );
+    Ok(())
+}
+
+fn read_session(path: &Path) -> Option<SecretKey> {
+    let session: Session = serde_yaml::from_str(&fs::read_to_string(path).ok()?).ok()?;
+
+    if session.expires <= OffsetDateTime::now_utc().unix_timestamp() {
+        debug!("Session expired.");
+        return None;
+    }
+
+    SecretKey::from_base64(&session.key)
+}
+
+/// Makes the file readable and writable only by its owner.
+pub fn restrict_permissions(path: &Path) -> Result<()> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(path, fs::Permissions::from_mode(0o600))?;
+    }
+    #[cfg(not(unix))]
+    let _ = path;
+
+    Ok(())
+}
+
+/// Reads the OS random source, failing rather than using weaker randomness.
+fn random_bytes<const N: usize>() -> Result<[u8; N]> {
+    let mut bytes = [0; N];
+    getrandom::getrandom(&mut bytes).map_err(|e| Error::NoRandomSource(e.to_string()))?;
+    Ok(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert_fs::TempDir;
+
+    fn make_encryption() -> (Encryption, SecretKey) {
+        Encryption::with_rounds("passphrase", 10).unwrap()
+    }
+
+    #[test]
+    fn encrypted_secrets_should_decrypt() {
+        let (_, key) = make_encryption();
+        let secret = b"PaSsWorD and a cookie longer than a single block";
+
+        let encrypted = key.encrypt(secret).unwrap();
+
+        assert!(!encrypted.contains("PaSsWorD"));
+        assert_ne!(encrypted, key.encrypt(secret).unwrap());
+        assert_eq!(key.decrypt(&encrypted).unwrap(), secret);
+    }
+
+    #[test]
+    fn tampered_secrets_should_not_decrypt() {
+        let (_, key) = make_encryption();
+        let mut blob = BASE64.decode(key.encrypt(b"PaSsWorD").unwrap()).unwrap();
+        blob[NONCE_LEN] ^= 1;
+
+        let result = key.decrypt(&BASE64.encode(blob));
+
+        assert!(matches!(result, Err(Error::SecretsCorrupted)));
+    }
+
+    #[test]
+    fn wrong_passphrase_should_fail() {
+        let (encryption, _) = make_encryption();
+
+        assert!(encryption.unlock("passphrase").is_ok());
+        assert!(matches!(
+            encryption.unlock("other"),
+            Err(Error::WrongPassphrase)
+        ));
+    }
+
+    #[test]
+    fn remembered_key_should_expire() {
+        let dir = TempDir::new().unwrap();
+        let paths = WorkspacePaths::with_root(dir.path()).with_user_dir(Some(dir.to_path_buf()));
+        let (mut encryption, key) = make_encryption();
+
+        remember(&paths, &encryption, &key).unwrap();
+        let remembered = read_session(&paths.session_path().unwrap()).unwrap();
+        assert!(encryption.verify(&remembered));
+
+        encryption.cache_minutes = 0;
+        remember(&paths, &encryption, &key).unwrap();
+        assert!(read_session(&paths.session_path().unwrap()).is_none());
+    }
+
+    #[test]
+    fn session_should_not_be_kept_in_workspace() {
+        let dir = TempDir::new().unwrap();
+        let paths = WorkspacePaths::with_root(dir.path());
+        let (encryption, key) = make_encryption();
+
+        remember(&paths, &encryption, &key).unwrap();
+
+        assert_eq!(paths.session_path(), None);
+        assert!(!paths.baca_dir().join("session").exists());
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn session_should_be_readable_only_by_user() {
+        use std::os::unix::fs::PermissionsExt;
+        let dir = TempDir::new().unwrap();
+        let paths = WorkspacePaths::with_root(dir.path()).with_user_dir(Some(dir.to_path_buf()));
+        let (encryption, key) = make_encryption();
+
+        remember(&paths, &encryption, &key).unwrap();
+
+        let mode = fs::metadata(paths.session_path().unwrap())
+            .unwrap()
+            .permissions()
+            .mode();
+        assert_eq!(mode & 0o777, 0o600);
+    }
+}
@@ -0,0 +1,331 @@
+use crate::error::{Error, Result};
+use crate::workspace::SubmitConfig;
+use glob::Pattern;
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+use tracing::{debug, info};
+
+/// A single file selected for submitting.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SourceFile {
+    /// The file as selected by the user.
+    pub original: PathBuf,
+    /// Current version of the file, after the transforms applied so far.
+    pub path: PathBuf,
+    /// Path of the file inside the submitted archive.
+    pub name: PathBuf,
+    /// Files packed inside, if this is an archive.
+    pub members: Vec<SourceFile>,
+}
+
+impl SourceFile {
+    pub fn new(path: PathBuf, name: PathBuf) -> Self {
+        Self {
+            original: path.clone(),
+            path,
+            name,
+            members: Vec::new(),
+        }
+    }
+
+    /// Files packed inside the archive, or the file itself.
+    pub fn files(&self) -> Vec<&SourceFile> {
+        match self.members.is_empty() {
+            true => vec![self],
+            false => self.members.iter().flat_map(|x| x.files()).collect(),
+        }
+    }
+
+    /// Directory inside `step_dir` where the transformed version of this file should be written.
+    pub fn output_dir(&self, step_dir: &Path) -> Result<PathBuf> {
+        let output_dir = match self.name.parent() {
+            Some(parent) => step_dir.join(parent),
+            None => step_dir.to_path_buf(),
+        };
+        fs::create_dir_all(&output_dir)?;
+
+        Ok(output_dir)
+    }
+}
+
+pub fn is_glob(entry: &str) -> bool {
+    entry.contains(['*', '?', '['])
+}
+
+/// Expands the files, directories and glob patterns from `submit_config` into the list of files to submit.
+pub fn select_sources(submit_config: &SubmitConfig) -> Result<Vec<SourceFile>> {
+    let include = make_patterns(&submit_config.include)?;
+    let exclude = make_patterns(&submit_config.exclude)?;
+
+    let mut seen = HashSet::new();
+    let mut sources = Vec::new();
+
+    for entry in submit_config.files() {
+        for (path, name) in expand_entry(entry)? {
+            if !is_selected(&name, &include, &exclude) {
+                debug!("Skipping {:?}", path);
+                continue;
+            }
+
+            if !seen.insert(path.canonicalize()?) {
+                continue;
+            }
+
+            let name = match submit_config.flatten {
+                true => PathBuf::from(path.file_name().ok_or(Error::InputFileDoesNotExist)?),
+                false => name,
+            };
+            sources.push(SourceFile::new(path, name));
+        }
+    }
+
+    if sources.is_empty() {
+        return Err(Error::NoSourceFiles);
+    }
+
+    let mut names = HashSet::new();
+    if let Some(duplicate) = sources.iter().find(|x| !names.insert(&x.name)) {
+        return Err(Error::DuplicateSourceName(
+            duplicate.name.to_string_lossy().to_string(),
+        ));
+    }
+
+    info!("Selected sources: {:?}", sources);
+    Ok(sources)
+}
+
+fn make_patterns(patterns: &[String]) -> Result<Vec<Pattern>> {
+    patterns
+        .iter()
+        .map(|x| Pattern::new(x).map_err(|_| Error::InvalidPattern(x.clone())))
+        .collect()
+}
+
+fn is_selected(name: &Path, include: &[Pattern], exclude: &[Pattern]) -> bool {
+    let included = include.is_empty() || include.iter().any(|x| x.matches_path(name));
+    let excluded = exclude.iter().any(|x| x.matches_path(name));
+
+    included && !excluded
+}
+
+/// Returns selected files along with their paths relative to the entry.
+fn expand_entry(entry: &Path) -> Result<Vec<(PathBuf, PathBuf)>> {
+    let entry_str = entry.to_string_lossy();
+
+    if is_glob(&entry_str) {
+        let base = glob_base(entry);
+        let mut files = Vec::new();
+
+        for path in
+            glob::glob(&entry_str).map_err(|_| Error::InvalidPattern(entry_str.to_string()))?
+        {
+            let path = path.map_err(|e| Error::Other(e.into()))?;
+
+            if path.is_file() {
+                let name = path.strip_prefix(&base).unwrap_or(&path).to_path_buf();
+                files.push((path, name));
+            }
+        }
+
+        return Ok(files);
+    }
+
+    if entry.is_dir() {
+        let mut files = Vec::new();
+        walk_dir(entry, entry, &mut files)?;
+        return Ok(files);
+    }
+
+    if entry.is_file() {
+        let name = entry.file_name().ok_or(Error::InputFileDoesNotExist)?;
+        return Ok(vec![(entry.to_path_buf(), PathBuf::from(name))]);
+    }
+
+    Err(Error::InputFileDoesNotExist)
+}
+
+/// Leading part of a glob pattern, which contains no wildcards.
+fn glob_base(pattern: &Path) -> PathBuf {
+    pattern
+        .components()
+        .take_while(|x| !matches!(x, Component::Normal(part) if is_glob(&part.to_string_lossy())))
+        .collect()
+}
+
+/// Recursively collects files under `dir`, skipping hidden files and directories.
+fn walk_dir(root: &Path, dir: &Path, files: &mut Vec<(PathBuf, PathBuf)>) -> Result<()> {
+    let mut entries = fs::read_dir(dir)?
+        .map(|x| x.map(|entry| entry.path()))
+        .collect::<std::io::Result<Vec<_>>>()?;
+    entries.sort();
+
+    for path in entries {
+        let hidden = path
+            .file_name()
+            .is_some_and(|x| x.to_string_lossy().starts_with('.'));
+
+        if hidden {
+            continue;
+        }
+
+        if path.is_dir() {
+            walk_dir(root, &path, files)?;
+        } else {
+            let name = path.strip_prefix(root).unwrap_or(&path).to_path_buf();
+            files.push((path, name));
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert_fs::prelude::*;
+    use assert_fs::TempDir;
+
+    fn make_project() -> TempDir {
+        let dir = TempDir::new().unwrap();
+        dir.child("main.cpp").touch().unwrap();
+        dir.child("include/matrix.h").touch().unwrap();
+        dir.child("src/matrix.cpp").touch().unwrap();
+        dir.child("src/matrix_test.cpp").touch().unwrap();
+        dir.child(".baca/submit").touch().unwrap();
+        dir
+    }
+
+    fn make_config(files: &[PathBuf]) -> SubmitConfig {
+        SubmitConfig {
+            files: files.to_vec(),
+            ..Default::default()
+        }
+    }
+
+    fn names(sources: &[SourceFile]) -> Vec<String> {
+        sources
+            .iter()
+            .map(|x| x.name.to_string_lossy().replace('\\', "/"))
+            .collect()
+    }
+
+    #[test]
+    fn single_file_should_be_selected_by_name() {
+        let dir = make_project();
+        let config = make_config(&[dir.join("src/matrix.cpp")]);
+
+        let sources = select_sources(&config).unwrap();
+
+        assert_eq!(names(&sources), vec!["matrix.cpp"]);
+        assert_eq!(sources[0].path, dir.join("src/matrix.cpp"));
+        assert_eq!(sources[0].original, sources[0].path);
+    }
+
+    #[test]
+    fn directory_should_be_walked_without_hidden_files() {
+        let dir = make_project();
+        let config = make_config(&[dir.to_path_buf()]);
+
+        let sources = select_sources(&config).unwrap();
+
+        assert_eq!(
+            names(&sources),
+            vec![
+                "include/matrix.h",
+                "main.cpp",
+                "src/matrix.cpp",
+                "src/matrix_test.cpp"
+            ]
+        );
+    }
+
+    #[test]
+    fn glob_should_keep_paths_relative_to_its_base() {
+        let dir = make_project();
+        let config = make_config(&[dir.join("**/matrix*")]);
+
+        let sources = select_sources(&config).unwrap();
+
+        assert_eq!(
+            names(&sources),
+            vec!["include/matrix.h", "src/matrix.cpp", "src/matrix_test.cpp"]
+        );
+    }
+
+    #[test]
+    fn include_and_exclude_should_filter_files() {
+        let dir = make_project();
+        let mut config = make_config(&[dir.to_path_buf()]);
+        config.include = vec!["*.cpp".to_string()];
+        config.exclude = vec!["*_test.cpp".to_string()];
+
+        let sources = select_sources(&config).unwrap();
+
+        assert_eq!(names(&sources), vec!["main.cpp", "src/matrix.cpp"]);
+    }
+
+    #[test]
+    fn flatten_should_drop_directories() {
+        let dir = make_project();
+        let mut config = make_config(&[dir.join("include"), dir.join("src")]);
+        config.flatten = true;
+
+        let sources = select_sources(&config).unwrap();
+
+        assert_eq!(
+            names(&sources),
+            vec!["matrix.h", "matrix.cpp", "matrix_test.cpp"]
+        );
+    }
+
+    #[test]
+    fn flatten_should_fail_on_name_clash() {
+        let dir = make_project();
+        dir.child("other/main.cpp").touch().unwrap();
+        let mut config = make_config(&[dir.to_path_buf()]);
+        config.flatten = true;
+
+        let result = select_sources(&config);
+
+        assert!(
+            matches!(&result, Err(Error::DuplicateSourceName(name)) if name == "main.cpp"),
+            "{:?}",
+            result
+        );
+    }
+
+    #[test]
+    fn file_selected_twice_should_be_submitted_once() {
+        let dir = make_project();
+        let config = make_config(&[dir.join("main.cpp"), dir.join("*.cpp")]);
+
+        let sources = select_sources(&config).unwrap();
+
+        assert_eq!(names(&sources), vec!["main.cpp"]);
+    }
+
+    #[test]
+    fn empty_selection_should_fail() {
+        let dir = make_project();
+        let config = make_config(&[dir.join("*.java")]);
+
+        let result = select_sources(&config);
+
+        assert!(matches!(result, Err(Error::NoSourceFiles)), "{:?}", result);
+    }
+
+    #[test]
+    fn missing_file_should_fail() {
+        let dir = make_project();
+        let config = make_config(&[dir.join("missing.cpp")]);
+
+        let result = select_sources(&config);
+
+        assert!(
+            matches!(result, Err(Error::InputFileDoesNotExist)),
+            "{:?}",
+            result
+        );
+    }
+}
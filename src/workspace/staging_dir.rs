@@ -0,0 +1,102 @@
+use crate::error::Result;
+use std::cell::Cell;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tempfile::TempDir;
+use tracing::debug;
+
+/// Unique temporary directory holding everything a single submit produces.
+///
+/// Every transform writes into its own numbered step directory, so the original file names
+/// are preserved and no step overwrites the output of another one.
+/// The directory is removed on drop, unless it was kept with [`StagingDir::keep`].
+pub struct StagingDir {
+    dir: TempDir,
+    steps: Cell<usize>,
+}
+
+impl StagingDir {
+    pub fn new() -> Result<Self> {
+        let dir = tempfile::Builder::new().prefix("baca-submit-").tempdir()?;
+        debug!("Staging directory: {:?}", dir.path());
+
+        Ok(Self {
+            dir,
+            steps: Cell::new(0),
+        })
+    }
+
+    pub fn path(&self) -> &Path {
+        self.dir.path()
+    }
+
+    /// Creates a fresh directory for the next transform.
+    pub fn step(&self, name: &str) -> Result<PathBuf> {
+        let number = self.steps.get() + 1;
+        self.steps.set(number);
+
+        let step_dir = self.path().join(format!("{:02}-{}", number, name));
+        fs::create_dir(&step_dir)?;
+        debug!("Staging step directory: {:?}", step_dir);
+
+        Ok(step_dir)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.steps.get() == 0
+    }
+
+    /// Persists the directory and returns its path.
+    pub fn keep(self) -> PathBuf {
+        self.dir.into_path()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn staging_dirs_should_be_unique() {
+        let first = StagingDir::new().unwrap();
+        let second = StagingDir::new().unwrap();
+
+        assert_ne!(first.path(), second.path());
+    }
+
+    #[test]
+    fn steps_should_not_overwrite_each_other() {
+        let staging = StagingDir::new().unwrap();
+
+        let first = staging.step("rename").unwrap();
+        let second = staging.step("rename").unwrap();
+
+        assert_ne!(first, second);
+        assert!(first.is_dir());
+        assert!(second.is_dir());
+        assert!(first.starts_with(staging.path()));
+        assert!(!staging.is_empty());
+    }
+
+    #[test]
+    fn dropped_staging_dir_should_be_removed() {
+        let staging = StagingDir::new().unwrap();
+        let path = staging.path().to_path_buf();
+        staging.step("zip").unwrap();
+
+        drop(staging);
+
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn kept_staging_dir_should_persist() {
+        let staging = StagingDir::new().unwrap();
+        let step = staging.step("no_main").unwrap();
+
+        let path = staging.keep();
+
+        assert!(step.exists());
+        fs::remove_dir_all(path).unwrap();
+    }
+}
@@ -0,0 +1,287 @@
+use crate::error::*;
+use crate::model::Language;
+use regex::Regex;
+use std::fs;
+use std::fs::File;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use tracing::{debug, info};
+
+const BEGIN_MARKER: &str = "baca:strip-begin";
+const END_MARKER: &str = "baca:strip-end";
+
+/// Removes code between `baca:strip-begin` and `baca:strip-end` comments and, if `strip_blocks`
+/// is set, C/C++ blocks compiled only locally, like `#ifdef DEBUG` or `#ifndef ONLINE_JUDGE`.
+pub fn strip_debug<P, Q>(
+    input_file: P,
+    language: &Language,
+    strip_blocks: bool,
+    output_dir: Q,
+) -> Result<PathBuf>
+where
+    P: AsRef<Path>,
+    Q: AsRef<Path>,
+{
+    let input_file: &Path = input_file.as_ref();
+    info!("Stripping debug code from {:?}", input_file);
+
+    let content = fs::read_to_string(input_file)?;
+    let mut content = strip_marked_regions(&content, language)?;
+
+    if strip_blocks && matches!(language, Language::Cpp | Language::CppWithFileSupport) {
+        content = strip_debug_blocks(&content)?;
+    }
+
+    let filepath = output_dir
+        .as_ref()
+        .join(input_file.file_name().ok_or(Error::InputFileDoesNotExist)?);
+    let mut file = File::create(filepath.clone())?;
+    file.write_all(content.as_ref())?;
+
+    debug!("New input file path: {:?}", filepath);
+    debug!("New input file content:\n{}", content);
+
+    Ok(filepath)
+}
+
+fn is_marker(line: &str, language: &Language, marker: &str) -> bool {
+    let line = line.trim_start();
+
+    language.comment_styles().is_some_and(|styles| {
+        styles.iter().any(|style| {
+            line.strip_prefix(style)
+                .is_some_and(|comment| comment.trim_start().starts_with(marker))
+        })
+    })
+}
+
+fn strip_marked_regions(content: &str, language: &Language) -> Result<String> {
+    let mut result = String::with_capacity(content.len());
+    let mut depth = 0;
+    let mut begin_line = 0;
+
+    for (number, line) in content.split_inclusive('\n').enumerate() {
+        if is_marker(line, language, BEGIN_MARKER) {
+            if depth == 0 {
+                begin_line = number + 1;
+            }
+            depth += 1;
+        } else if is_marker(line, language, END_MARKER) {
+            if depth == 0 {
+                return Err(Error::UnmatchedStripMarker(
+                    END_MARKER.to_string(),
+                    number + 1,
+                ));
+            }
+            depth -= 1;
+        } else if depth == 0 {
+            result.push_str(line);
+        }
+    }
+
+    if depth > 0 {
+        return Err(Error::UnmatchedStripMarker(
+            BEGIN_MARKER.to_string(),
+            begin_line,
+        ));
+    }
+
+    Ok(result)
+}
+
+enum BlockState {
+    Normal,
+    /// Inside a debug block, `depth` counts nested conditionals.
+    Skipping {
+        depth: usize,
+    },
+    /// Inside the `#else` branch of a debug block.
+    KeepingElse {
+        depth: usize,
+    },
+}
+
+fn strip_debug_blocks(content: &str) -> Result<String> {
+    let debug_start =
+        Regex::new(r"^\s*#\s*(ifdef\s+(DEBUG|_DEBUG|LOCAL)|ifndef\s+ONLINE_JUDGE)\b").unwrap();
+    let if_start = Regex::new(r"^\s*#\s*if").unwrap();
+    let else_start = Regex::new(r"^\s*#\s*else\b").unwrap();
+    let elif_start = Regex::new(r"^(\s*#\s*)elif\b").unwrap();
+    let endif = Regex::new(r"^\s*#\s*endif\b").unwrap();
+
+    let mut result = String::with_capacity(content.len());
+    let mut state = BlockState::Normal;
+    let mut begin = (String::new(), 0);
+
+    for (number, line) in content.split_inclusive('\n').enumerate() {
+        state = match state {
+            BlockState::Normal if debug_start.is_match(line) => {
+                begin = (line.trim().to_string(), number + 1);
+                BlockState::Skipping { depth: 0 }
+            }
+            BlockState::Normal => {
+                result.push_str(line);
+                BlockState::Normal
+            }
+            BlockState::Skipping { depth } if if_start.is_match(line) => {
+                BlockState::Skipping { depth: depth + 1 }
+            }
+            BlockState::Skipping { depth: 0 } if endif.is_match(line) => BlockState::Normal,
+            BlockState::Skipping { depth } if endif.is_match(line) => {
+                BlockState::Skipping { depth: depth - 1 }
+            }
+            BlockState::Skipping { depth: 0 } if else_start.is_match(line) => {
+                BlockState::KeepingElse { depth: 0 }
+            }
+            BlockState::Skipping { depth: 0 } if elif_start.is_match(line) => {
+                // The rest of the chain becomes a regular conditional, closed by the original #endif.
+                result.push_str(&elif_start.replace(line, "${1}if"));
+                BlockState::Normal
+            }
+            BlockState::Skipping { depth } => BlockState::Skipping { depth },
+            BlockState::KeepingElse { depth: 0 } if endif.is_match(line) => BlockState::Normal,
+            BlockState::KeepingElse { depth } => {
+                result.push_str(line);
+
+                if if_start.is_match(line) {
+                    BlockState::KeepingElse { depth: depth + 1 }
+                } else if endif.is_match(line) {
+                    BlockState::KeepingElse { depth: depth - 1 }
+                } else {
+                    BlockState::KeepingElse { depth }
+                }
+            }
+        };
+    }
+
+    if !matches!(state, BlockState::Normal) {
+        return Err(Error::UnmatchedStripMarker(begin.0, begin.1));
+    }
+
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn marked_region_should_be_removed() {
+        let input = r#"// Hubert Jaremko
+int solve(int x) {
+    // baca:strip-begin
+    std::cerr << "x = " << x << std::endl;
+    // baca:strip-end
+    return x * 2;
+}
+"#;
+        let expected = r#"// Hubert Jaremko
+int solve(int x) {
+    return x * 2;
+}
+"#;
+
+        assert_eq!(
+            strip_marked_regions(input, &Language::Cpp).unwrap(),
+            expected
+        );
+    }
+
+    #[test]
+    fn markers_should_follow_language_comments() {
+        let bash = "# Hubert Jaremko\n#baca:strip-begin\nset -x\n# baca:strip-end\necho 1\n";
+        let ada =
+            "-- Hubert Jaremko\n-- baca:strip-begin\nPut_Line(\"debug\");\n-- baca:strip-end\n";
+
+        assert_eq!(
+            strip_marked_regions(bash, &Language::Bash).unwrap(),
+            "# Hubert Jaremko\necho 1\n"
+        );
+        assert_eq!(
+            strip_marked_regions(ada, &Language::Ada).unwrap(),
+            "-- Hubert Jaremko\n"
+        );
+    }
+
+    #[test]
+    fn markers_of_other_languages_should_be_kept() {
+        let input = "# baca:strip-begin\nint x;\n# baca:strip-end\n";
+
+        assert_eq!(strip_marked_regions(input, &Language::Java).unwrap(), input);
+    }
+
+    #[test]
+    fn unmatched_marker_should_fail() {
+        let input = "// Hubert Jaremko\n// baca:strip-begin\nint x;\n";
+
+        let result = strip_marked_regions(input, &Language::Cpp);
+
+        assert!(
+            matches!(&result, Err(Error::UnmatchedStripMarker(marker, 2)) if marker == BEGIN_MARKER),
+            "{:?}",
+            result
+        );
+    }
+
+    #[test]
+    fn debug_block_should_be_removed() {
+        let input = r#"int main() {
+#ifdef DEBUG
+    freopen("in.txt", "r", stdin);
+#if 1
+    dump();
+#endif
+#endif
+    solve();
+}
+"#;
+        let expected = "int main() {\n    solve();\n}\n";
+
+        assert_eq!(strip_debug_blocks(input).unwrap(), expected);
+    }
+
+    #[test]
+    fn else_branch_should_be_kept() {
+        let input = r#"#ifndef ONLINE_JUDGE
+#define LOG(x) std::cerr << x
+#else
+#ifdef _WIN32
+#define LOG(x)
+#endif
+#define LOG(x)
+#endif
+int x;
+"#;
+        let expected = "#ifdef _WIN32\n#define LOG(x)\n#endif\n#define LOG(x)\nint x;\n";
+
+        assert_eq!(strip_debug_blocks(input).unwrap(), expected);
+    }
+
+    #[test]
+    fn elif_should_become_if() {
+        let input = "#ifdef DEBUG\nint a;\n#elif defined(FAST)\nint b;\n#endif\n";
+
+        assert_eq!(
+            strip_debug_blocks(input).unwrap(),
+            "#if defined(FAST)\nint b;\n#endif\n"
+        );
+    }
+
+    #[test]
+    fn other_conditionals_should_be_kept() {
+        let input = "#ifdef _WIN32\nint a;\n#endif\n";
+
+        assert_eq!(strip_debug_blocks(input).unwrap(), input);
+    }
+
+    #[test]
+    fn unterminated_debug_block_should_fail() {
+        let result = strip_debug_blocks("int a;\n#ifdef DEBUG\nint b;\n");
+
+        assert!(
+            matches!(result, Err(Error::UnmatchedStripMarker(_, 2))),
+            "{:?}",
+            result
+        );
+    }
+}
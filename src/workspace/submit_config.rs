@@ -1,8 +1,11 @@
 use crate::error::Error;
 use crate::model::Language;
+use crate::transform::{BuiltinTransform, TransformConfig};
+use crate::workspace::normalize::Transliterate;
+use crate::workspace::source_files::is_glob;
 use crate::workspace::{ConfigObject, Workspace};
 use merge::Merge;
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize};
 use std::path::Path;
 use std::path::PathBuf;
 
@@ -12,24 +15,150 @@ fn merge_left_option<T>(left: &mut Option<T>, right: Option<T>) {
     }
 }
 
+fn merge_left_vec<T>(left: &mut Vec<T>, right: Vec<T>) {
+    if !right.is_empty() {
+        *left = right;
+    }
+}
+
+/// Accepts both a single path, as saved by older versions, and a list of paths.
+fn one_or_many<'de, D>(deserializer: D) -> Result<Vec<PathBuf>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum OneOrMany {
+        One(PathBuf),
+        Many(Vec<PathBuf>),
+    }
+
+    Ok(match Option::<OneOrMany>::deserialize(deserializer)? {
+        None => Vec::new(),
+        Some(OneOrMany::One(path)) => vec![path],
+        Some(OneOrMany::Many(paths)) => paths,
+    })
+}
+
+/// The `header` section of the submit config.
+#[derive(Debug, Default, Serialize, Deserialize, PartialEq, Eq, Merge, Clone)]
+pub struct HeaderConfig {
+    /// Header added to files without one, supports `{name}`, `{login}` and `{task}`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[merge(strategy = merge_left_option)]
+    pub template: Option<String>,
+    /// Author name, the login is used if not set.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[merge(strategy = merge_left_option)]
+    pub name: Option<String>,
+    #[serde(default)]
+    #[merge(strategy = merge::bool::overwrite_false)]
+    pub add: bool,
+    /// Require existing headers to contain the name or the login.
+    #[serde(default)]
+    #[merge(strategy = merge::bool::overwrite_false)]
+    pub strict: bool,
+}
+
+impl HeaderConfig {
+    const DEFAULT_TEMPLATE: &'static str = "{name}";
+
+    pub fn template(&self) -> &str {
+        self.template.as_deref().unwrap_or(Self::DEFAULT_TEMPLATE)
+    }
+
+    /// Values, which an existing header has to contain one of.
+    pub fn expected_authors(&self, login: &str) -> Vec<String> {
+        self.name
+            .iter()
+            .cloned()
+            .chain(std::iter::once(login.to_string()))
+            .filter(|x| !x.trim().is_empty())
+            .collect()
+    }
+
+    fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// The `normalize` section of the submit config.
+#[derive(Debug, Default, Serialize, Deserialize, PartialEq, Eq, Merge, Clone)]
+pub struct NormalizeConfig {
+    #[serde(default)]
+    #[merge(strategy = merge::bool::overwrite_false)]
+    pub strip_bom: bool,
+    #[serde(default)]
+    #[merge(strategy = merge::bool::overwrite_false)]
+    pub crlf_to_lf: bool,
+    /// Encoding of the sources, e.g. `windows-1250`, UTF-8 if not set.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[merge(strategy = merge_left_option)]
+    pub encoding: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[merge(strategy = merge_left_option)]
+    pub transliterate: Option<Transliterate>,
+}
+
+impl NormalizeConfig {
+    pub fn is_enabled(&self) -> bool {
+        !self.is_default()
+    }
+
+    fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
 #[derive(Debug, Default, Serialize, Deserialize, PartialEq, Eq, Merge, Clone)]
 pub struct SubmitConfig {
     #[merge(strategy = merge_left_option)]
     pub id: Option<String>,
-    #[merge(strategy = merge_left_option)]
-    pub file: Option<PathBuf>,
+    /// Files, directories or glob patterns.
+    #[serde(alias = "file", default, deserialize_with = "one_or_many")]
+    #[merge(strategy = merge_left_vec)]
+    pub files: Vec<PathBuf>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    #[merge(strategy = merge_left_vec)]
+    pub include: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    #[merge(strategy = merge_left_vec)]
+    pub exclude: Vec<String>,
+    #[serde(default)]
+    #[merge(strategy = merge::bool::overwrite_false)]
+    pub flatten: bool,
     #[merge(strategy = merge::bool::overwrite_false)]
     pub to_zip: bool,
     #[merge(strategy = merge_left_option)]
     pub language: Option<Language>,
     #[merge(strategy = merge_left_option)]
     pub rename_as: Option<String>,
+    #[serde(default, skip_serializing_if = "NormalizeConfig::is_default")]
+    pub normalize: NormalizeConfig,
+    #[serde(default)]
+    #[merge(strategy = merge::bool::overwrite_false)]
+    pub inline_includes: bool,
+    #[serde(default)]
+    #[merge(strategy = merge::bool::overwrite_false)]
+    pub strip_debug: bool,
+    #[serde(default)]
+    #[merge(strategy = merge::bool::overwrite_false)]
+    pub strip_debug_blocks: bool,
     #[merge(strategy = merge::bool::overwrite_false)]
     pub no_main: bool,
     #[merge(strategy = merge::bool::overwrite_false)]
     pub no_polish: bool,
     #[merge(strategy = merge::bool::overwrite_false)]
     pub skip_header: bool,
+    #[serde(default, skip_serializing_if = "HeaderConfig::is_default")]
+    pub header: HeaderConfig,
+    #[serde(default)]
+    #[merge(strategy = merge::bool::overwrite_false)]
+    pub compile: bool,
+    /// Ordered list of transforms applied to every file before submitting.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    #[merge(strategy = merge_left_vec)]
+    pub transforms: Vec<TransformConfig>,
 }
 
 impl SubmitConfig {
@@ -43,13 +172,23 @@ impl SubmitConfig {
     ) -> Self {
         Self {
             id: id.to_string().into(),
-            file: file.to_owned().into(),
+            files: vec![file.to_owned()],
+            include: Vec::new(),
+            exclude: Vec::new(),
+            flatten: false,
             to_zip,
             language: language.into(),
             rename_as,
+            normalize: NormalizeConfig::default(),
+            inline_includes: false,
+            strip_debug: false,
+            strip_debug_blocks: false,
             no_main: false,
             no_polish: false,
             skip_header: false,
+            header: HeaderConfig::default(),
+            compile: false,
+            transforms: Vec::new(),
         }
     }
 
@@ -57,27 +196,77 @@ impl SubmitConfig {
         self.id.as_ref()
     }
 
+    pub fn files(&self) -> &[PathBuf] {
+        &self.files
+    }
+
+    /// Returns the selected file, if it is the only one and not a directory or a pattern.
     pub fn file(&self) -> Option<&Path> {
-        self.file.as_deref()
+        match self.files.as_slice() {
+            [file] if file.is_file() => Some(file),
+            _ => None,
+        }
     }
 
-    pub fn try_set_file<P>(&mut self, filepath: Option<P>) -> crate::error::Result<()>
+    /// Declared transforms, extended with the ones enabled by switches.
+    /// Switched on transforms, which are not declared, run in the default order:
+    /// normalize, add-header, inline-includes, strip-debug, no-main, no-polish, zip.
+    pub fn transforms(&self) -> Vec<TransformConfig> {
+        let mut transforms = self.transforms.clone();
+        let switches = [
+            (self.normalize.is_enabled(), BuiltinTransform::Normalize),
+            (self.header.add, BuiltinTransform::AddHeader),
+            (self.inline_includes, BuiltinTransform::InlineIncludes),
+            (
+                self.strip_debug || self.strip_debug_blocks,
+                BuiltinTransform::StripDebug,
+            ),
+            (self.no_main, BuiltinTransform::NoMain),
+            (self.no_polish, BuiltinTransform::NoPolish),
+            (self.to_zip, BuiltinTransform::Zip),
+        ];
+        let zip = TransformConfig::from(BuiltinTransform::Zip);
+
+        for (enabled, transform) in switches {
+            let transform = TransformConfig::from(transform);
+
+            if !enabled || transforms.contains(&transform) {
+                continue;
+            }
+
+            match transforms.iter().position(|x| *x == zip) {
+                Some(position) => transforms.insert(position, transform),
+                None => transforms.push(transform),
+            }
+        }
+
+        transforms
+    }
+
+    /// Sets the selection, plain paths must exist and are saved as absolute.
+    pub fn try_set_files<S>(&mut self, entries: &[S]) -> crate::error::Result<()>
     where
-        P: Into<PathBuf>,
+        S: AsRef<str>,
     {
-        let file = match filepath {
-            None => None,
-            Some(file) => {
-                let path = file.into();
+        let mut files = Vec::new();
 
-                if !path.exists() {
-                    return Err(Error::InputFileDoesNotExist);
-                }
+        for entry in entries {
+            let entry = entry.as_ref();
+            let path = PathBuf::from(entry);
 
-                Some(path.canonicalize()?)
+            if is_glob(entry) {
+                files.push(std::env::current_dir()?.join(path));
+                continue;
             }
-        };
-        self.file = file;
+
+            if !path.exists() {
+                return Err(Error::InputFileDoesNotExist);
+            }
+
+            files.push(path.canonicalize()?);
+        }
+
+        self.files = files;
         Ok(())
     }
 }
@@ -88,7 +277,15 @@ impl ConfigObject for SubmitConfig {
     }
 
     fn read_config<W: Workspace>(workspace: &W) -> crate::error::Result<Self> {
-        workspace.read_config_object::<Self>()
+        let mut config = workspace.read_config_object::<Self>()?;
+
+        // Paths written by hand are relative to the workspace, not to the current directory.
+        if config.files.iter().any(|x| x.is_relative()) {
+            let root = workspace.get_paths().root().to_path_buf();
+            config.files = config.files.iter().map(|x| root.join(x)).collect();
+        }
+
+        Ok(config)
     }
 
     fn remove_config<W: Workspace>(workspace: &W) -> crate::error::Result<()> {
@@ -185,8 +382,13 @@ mod tests {
         input_file.touch().unwrap();
         let submit_config_first =
             SubmitConfig::new("2", input_file.path(), false, Language::Bash, None);
-        let submit_config_second =
-            SubmitConfig::new("3", Path::new("bar.cpp"), false, Language::Cpp, None);
+        let submit_config_second = SubmitConfig::new(
+            "3",
+            &temp_dir.path().join("bar.cpp"),
+            false,
+            Language::Cpp,
+            None,
+        );
 
         workspace.initialize().unwrap();
         submit_config_first.save_config(&workspace).unwrap();
@@ -200,6 +402,26 @@ mod tests {
         temp_dir.close().unwrap();
     }
 
+    #[test]
+    fn relative_files_should_be_resolved_against_workspace() {
+        let (temp_dir, mock_paths, workspace) = make_temp_workspace().unwrap();
+        workspace.initialize().unwrap();
+        ChildPath::new(mock_paths.config_path::<SubmitConfig>())
+            .write_str("---\nid: \"2\"\nfile:\n  - src/main.cpp\n  - /tmp/foo.h\nto_zip: false\nlanguage: ~\nrename_as: ~\nno_main: false\nno_polish: false\nskip_header: false\n")
+            .unwrap();
+
+        let submit_config = SubmitConfig::read_config(&workspace).unwrap();
+
+        assert_eq!(
+            submit_config.files,
+            vec![
+                temp_dir.path().join("src/main.cpp"),
+                PathBuf::from("/tmp/foo.h")
+            ]
+        );
+        temp_dir.close().unwrap();
+    }
+
     #[test]
     fn remove_submit_config() {
         let (temp_dir, mock_paths, workspace) = make_temp_workspace().unwrap();
@@ -220,13 +442,18 @@ mod tests {
     fn default_should_contain_none() {
         let default = SubmitConfig::default();
 
-        assert!(default.file.is_none());
+        assert!(default.files.is_empty());
+        assert!(default.include.is_empty());
+        assert!(default.exclude.is_empty());
+        assert!(!default.flatten);
         assert!(default.language.is_none());
         assert!(default.id.is_none());
         assert!(default.rename_as.is_none());
         assert!(!default.to_zip);
+        assert!(!default.strip_debug);
         assert!(!default.no_main);
         assert!(!default.no_polish);
+        assert!(!default.compile);
     }
 
     #[test]
@@ -236,7 +463,7 @@ mod tests {
         lhs.merge(rhs);
         let merged = lhs;
 
-        assert!(merged.file.is_none());
+        assert!(merged.files.is_empty());
         assert!(merged.language.is_none());
         assert!(merged.id.is_none());
         assert!(merged.rename_as.is_none());
@@ -248,13 +475,30 @@ mod tests {
     fn make_submit_config() -> SubmitConfig {
         SubmitConfig {
             id: "3".to_string().into(),
-            file: PathBuf::from("file.txt").into(),
+            files: vec![PathBuf::from("file.txt")],
+            include: vec!["*.cpp".to_string()],
+            exclude: Vec::new(),
+            flatten: true,
             to_zip: true,
             language: Language::from_str("C++").unwrap().into(),
             rename_as: "source.cpp".to_string().into(),
+            normalize: NormalizeConfig {
+                crlf_to_lf: true,
+                ..Default::default()
+            },
+            inline_includes: false,
+            strip_debug: true,
+            strip_debug_blocks: false,
             no_main: true,
             no_polish: true,
             skip_header: false,
+            header: HeaderConfig {
+                name: Some("Hubert Jaremko".to_string()),
+                add: true,
+                ..Default::default()
+            },
+            compile: true,
+            transforms: Vec::new(),
         }
     }
 
@@ -266,13 +510,20 @@ mod tests {
         lhs.merge(rhs);
         let merged = lhs;
 
-        assert_eq!(merged.file.unwrap().to_str().unwrap(), "file.txt");
+        assert_eq!(merged.files, vec![PathBuf::from("file.txt")]);
+        assert_eq!(merged.include, vec!["*.cpp"]);
+        assert!(merged.flatten);
         assert_eq!(merged.language.unwrap(), Language::Cpp);
         assert_eq!(merged.id.unwrap(), "3");
         assert_eq!(merged.rename_as.unwrap(), "source.cpp");
         assert!(merged.to_zip);
+        assert!(merged.strip_debug);
         assert!(merged.no_main);
         assert!(merged.no_polish);
+        assert!(merged.compile);
+        assert!(merged.header.add);
+        assert!(merged.normalize.crlf_to_lf);
+        assert_eq!(merged.header.name.unwrap(), "Hubert Jaremko");
     }
 
     #[test]
@@ -283,7 +534,7 @@ mod tests {
         rhs.merge(lhs);
         let merged = rhs;
 
-        assert_eq!(merged.file.unwrap().to_str().unwrap(), "file.txt");
+        assert_eq!(merged.files, vec![PathBuf::from("file.txt")]);
         assert_eq!(merged.language.unwrap(), Language::Java);
         assert_eq!(merged.id.unwrap(), "3");
         assert_eq!(merged.rename_as.unwrap(), "source.cpp");
@@ -291,4 +542,137 @@ mod tests {
         assert!(merged.no_main);
         assert!(merged.no_polish);
     }
+
+    #[test]
+    fn merge_should_replace_files() {
+        let mut lhs = make_submit_config();
+        let rhs = SubmitConfig {
+            files: vec![PathBuf::from("a.cpp"), PathBuf::from("b.h")],
+            ..Default::default()
+        };
+
+        lhs.merge(rhs);
+
+        assert_eq!(
+            lhs.files,
+            vec![PathBuf::from("a.cpp"), PathBuf::from("b.h")]
+        );
+        assert_eq!(lhs.include, vec!["*.cpp"]);
+    }
+
+    #[test]
+    fn single_file_from_older_version_should_be_read() {
+        let (temp_dir, mock_paths, workspace) = make_temp_workspace().unwrap();
+        workspace.initialize().unwrap();
+        ChildPath::new(mock_paths.config_path::<SubmitConfig>())
+            .write_str(
+                "---\nid: \"2\"\nfile: /tmp/foo.sh\nto_zip: false\nlanguage: Bash\nrename_as: ~\nno_main: false\nno_polish: false\nskip_header: false\n",
+            )
+            .unwrap();
+
+        let submit_config = SubmitConfig::read_config(&workspace).unwrap();
+
+        assert_eq!(submit_config.files, vec![PathBuf::from("/tmp/foo.sh")]);
+        temp_dir.close().unwrap();
+    }
+
+    #[test]
+    fn missing_file_should_be_read_as_empty_selection() {
+        let (temp_dir, mock_paths, workspace) = make_temp_workspace().unwrap();
+        workspace.initialize().unwrap();
+        ChildPath::new(mock_paths.config_path::<SubmitConfig>())
+            .write_str("---\nid: \"2\"\nfile: ~\nto_zip: false\nlanguage: ~\nrename_as: ~\nno_main: false\nno_polish: false\nskip_header: false\n")
+            .unwrap();
+
+        let submit_config = SubmitConfig::read_config(&workspace).unwrap();
+
+        assert!(submit_config.files.is_empty());
+        temp_dir.close().unwrap();
+    }
+
+    #[test]
+    fn globs_should_not_be_required_to_exist() {
+        let mut submit_config = SubmitConfig::default();
+
+        submit_config.try_set_files(&["src/*.cpp"]).unwrap();
+
+        assert!(submit_config.files[0].is_absolute());
+        assert!(submit_config.files[0].ends_with("src/*.cpp"));
+    }
+
+    #[test]
+    fn missing_file_should_not_be_set() {
+        let mut submit_config = SubmitConfig::default();
+
+        let result = submit_config.try_set_files(&["surely/missing.cpp"]);
+
+        assert!(matches!(result, Err(Error::InputFileDoesNotExist)));
+    }
+
+    #[test]
+    fn switches_should_use_default_transform_order() {
+        let mut submit_config = make_submit_config();
+        submit_config.to_zip = true;
+        submit_config.inline_includes = true;
+        submit_config.no_main = true;
+        submit_config.no_polish = true;
+
+        assert_eq!(
+            submit_config.transforms(),
+            vec![
+                BuiltinTransform::Normalize.into(),
+                BuiltinTransform::AddHeader.into(),
+                BuiltinTransform::InlineIncludes.into(),
+                BuiltinTransform::StripDebug.into(),
+                BuiltinTransform::NoMain.into(),
+                BuiltinTransform::NoPolish.into(),
+                BuiltinTransform::Zip.into()
+            ]
+        );
+    }
+
+    #[test]
+    fn switches_should_not_reorder_declared_transforms() {
+        let mut submit_config = make_submit_config();
+        submit_config.to_zip = true;
+        submit_config.strip_debug = false;
+        submit_config.header.add = false;
+        submit_config.normalize = NormalizeConfig::default();
+        submit_config.no_main = true;
+        submit_config.no_polish = false;
+        submit_config.transforms = vec![
+            TransformConfig::External {
+                command: "clang-format".to_string(),
+            },
+            BuiltinTransform::NoPolish.into(),
+            BuiltinTransform::Zip.into(),
+        ];
+
+        assert_eq!(
+            submit_config.transforms(),
+            vec![
+                TransformConfig::External {
+                    command: "clang-format".to_string(),
+                },
+                BuiltinTransform::NoPolish.into(),
+                BuiltinTransform::NoMain.into(),
+                BuiltinTransform::Zip.into(),
+            ]
+        );
+    }
+
+    #[test]
+    fn header_section_should_be_read() {
+        let yaml = "---\nid: \"2\"\nfile: /tmp/foo.cpp\nto_zip: false\nlanguage: Cpp\nrename_as: ~\nno_main: false\nno_polish: false\nskip_header: false\nheader:\n  template: \"// {name} ({login})\"\n  strict: true\n";
+
+        let submit_config: SubmitConfig = serde_yaml::from_str(yaml).unwrap();
+
+        assert_eq!(submit_config.header.template(), "// {name} ({login})");
+        assert!(submit_config.header.strict);
+        assert!(!submit_config.header.add);
+        assert_eq!(
+            submit_config.header.expected_authors("hjaremko"),
+            vec!["hjaremko"]
+        );
+    }
 }
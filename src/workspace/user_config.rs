@@ -0,0 +1,256 @@
+use crate::error::{Error, Result};
+use crate::log;
+use crate::workspace::secret_store::{restrict_permissions, unlock};
+use crate::workspace::{ConfigObject, Encryption, SecretKey, Workspace};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::ops::Not;
+use tracing::info;
+
+/// Credentials and preferences shared by the workspaces referencing the profile.
+#[derive(Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Profile {
+    pub login: String,
+    /// Plain text, moved to `secret` once the encryption is set up.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub password: String,
+    /// The password encrypted like the workspace secrets.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub secret: Option<String>,
+    /// Command printing the password, used instead of storing it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub password_command: Option<String>,
+    /// Host used by `init` when none is given.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub host: Option<String>,
+    /// Disables the update check, like `--no-update`.
+    #[serde(default, skip_serializing_if = "Not::not")]
+    pub no_update: bool,
+}
+
+/// Masks the password, so the profile can be logged.
+impl fmt::Debug for Profile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Profile")
+            .field("login", &self.login)
+            .field("password", &log::mask(&self.password))
+            .field("secret", &self.secret.as_deref().map(log::mask))
+            .field("password_command", &self.password_command)
+            .field("host", &self.host)
+            .field("no_update", &self.no_update)
+            .finish()
+    }
+}
+
+/// Named profiles, kept in the user config directory.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UserConfig {
+    /// Profile used outside of workspaces.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_profile: Option<String>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub profiles: BTreeMap<String, Profile>,
+    /// Encrypts the secrets of all workspaces when set up.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub encryption: Option<Encryption>,
+}
+
+impl UserConfig {
+    pub fn profile(&self, name: &str) -> Result<&Profile> {
+        self.profiles
+            .get(name)
+            .ok_or_else(|| Error::ProfileNotFound(name.to_string()))
+    }
+
+    /// The given profile, or the default one.
+    pub fn active_profile(&self, name: Option<&str>) -> Option<&Profile> {
+        name.or(self.default_profile.as_deref())
+            .and_then(|x| self.profiles.get(x))
+    }
+
+    pub fn profile_names(&self) -> Vec<String> {
+        self.profiles.keys().cloned().collect()
+    }
+
+    /// The password of the profile, decrypted when it is encrypted.
+    pub fn profile_password<W: Workspace>(
+        &self,
+        workspace: &W,
+        profile: &Profile,
+    ) -> Result<String> {
+        let secret = match &profile.secret {
+            Some(secret) => secret,
+            None => return Ok(profile.password.clone()),
+        };
+
+        let encryption = self.encryption.as_ref().ok_or(Error::EncryptionNotSetUp)?;
+        let password = unlock(workspace, encryption)?.decrypt(secret)?;
+        let password = String::from_utf8(password).map_err(|_| Error::SecretsCorrupted)?;
+        log::register_secret(&password);
+        Ok(password)
+    }
+
+    fn has_plaintext_passwords(&self) -> bool {
+        self.profiles.values().any(|x| !x.password.is_empty())
+    }
+
+    fn encrypt_passwords(mut self, key: &SecretKey) -> Result<Self> {
+        for profile in self.profiles.values_mut() {
+            if !profile.password.is_empty() {
+                let password = std::mem::take(&mut profile.password);
+                profile.secret = Some(key.encrypt(password.as_bytes())?);
+            }
+        }
+
+        Ok(self)
+    }
+
+    /// Encrypts the profile passwords saved before the encryption was set up. Returns whether anything changed.
+    pub fn encrypt_plaintext<W: Workspace>(workspace: &W) -> Result<bool> {
+        let config = Self::read_config(workspace)?;
+
+        if config.encryption.is_none() || !config.has_plaintext_passwords() {
+            return Ok(false);
+        }
+
+        info!("Encrypting plaintext profile passwords.");
+        config.save_config(workspace)?;
+        Ok(true)
+    }
+}
+
+impl ConfigObject for UserConfig {
+    /// Encrypts the profile passwords when the encryption is set up, the file is readable only by the user.
+    fn save_config<W: Workspace>(&self, workspace: &W) -> Result<()> {
+        let config = match &self.encryption {
+            Some(encryption) if self.has_plaintext_passwords() => self
+                .clone()
+                .encrypt_passwords(&unlock(workspace, encryption)?)?,
+            _ => self.clone(),
+        };
+
+        workspace.save_config_object(&config)?;
+        restrict_permissions(&workspace.get_paths().config_path::<Self>())
+    }
+
+    fn read_config<W: Workspace>(workspace: &W) -> Result<Self> {
+        match workspace.read_config_object::<Self>() {
+            Err(Error::ReadingConfig(_)) | Err(Error::WorkspaceNotInitialized) => {
+                Ok(Self::default())
+            }
+            result => result,
+        }
+    }
+
+    fn remove_config<W: Workspace>(workspace: &W) -> Result<()> {
+        workspace.remove_config_object::<Self>()
+    }
+
+    fn config_filename() -> String {
+        "profiles".to_string()
+    }
+
+    fn is_global() -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::workspace::workspace_dir::tests::make_temp_workspace;
+    use crate::workspace::{WorkspaceDir, WorkspacePaths};
+    use assert_fs::prelude::*;
+    use assert_fs::TempDir;
+
+    #[test]
+    fn profiles_should_be_shared_by_workspaces() {
+        let user_dir = TempDir::new().unwrap();
+        let make_workspace = |root: &TempDir| {
+            WorkspaceDir::with_paths(
+                WorkspacePaths::with_root(root.path()).with_user_dir(Some(user_dir.to_path_buf())),
+            )
+        };
+        let first_dir = TempDir::new().unwrap();
+        let second_dir = TempDir::new().unwrap();
+        let first = make_workspace(&first_dir);
+        let second = make_workspace(&second_dir);
+        first.initialize().unwrap();
+        second.initialize().unwrap();
+
+        let mut config = UserConfig::default();
+        config.profiles.insert(
+            "uj".to_string(),
+            Profile {
+                login: "jaremko".to_string(),
+                password: "pass".to_string(),
+                ..Default::default()
+            },
+        );
+        config.save_config(&first).unwrap();
+
+        assert_eq!(UserConfig::read_config(&second).unwrap(), config);
+        user_dir
+            .child("profiles")
+            .assert(predicates::path::exists());
+        first_dir
+            .child(".baca/profiles")
+            .assert(predicates::path::missing());
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn profiles_should_be_readable_only_by_user() {
+        use std::os::unix::fs::PermissionsExt;
+        let user_dir = TempDir::new().unwrap();
+        let root = TempDir::new().unwrap();
+        let workspace = WorkspaceDir::with_paths(
+            WorkspacePaths::with_root(root.path()).with_user_dir(Some(user_dir.to_path_buf())),
+        );
+
+        UserConfig::default().save_config(&workspace).unwrap();
+
+        let mode = std::fs::metadata(user_dir.child("profiles").path())
+            .unwrap()
+            .permissions()
+            .mode();
+        assert_eq!(mode & 0o777, 0o600);
+    }
+
+    #[test]
+    fn missing_config_should_have_no_profiles() {
+        let (temp_dir, _, workspace) = make_temp_workspace().unwrap();
+
+        let config = UserConfig::read_config(&workspace).unwrap();
+
+        assert!(config.profiles.is_empty());
+        assert!(matches!(
+            config.profile("uj"),
+            Err(Error::ProfileNotFound(_))
+        ));
+        temp_dir.close().unwrap();
+    }
+
+    #[test]
+    fn default_profile_should_be_active_without_name() {
+        let config = UserConfig {
+            default_profile: Some("uj".to_string()),
+            profiles: BTreeMap::from([
+                ("uj".to_string(), Profile::default()),
+                (
+                    "other".to_string(),
+                    Profile {
+                        no_update: true,
+                        ..Default::default()
+                    },
+                ),
+            ]),
+            encryption: None,
+        };
+
+        assert_eq!(config.active_profile(None), Some(&Profile::default()));
+        assert!(config.active_profile(Some("other")).unwrap().no_update);
+        assert_eq!(config.active_profile(Some("missing")), None);
+    }
+}
@@ -16,7 +16,7 @@ impl WorkspaceDir {
         }
     }
 
-    pub(crate) fn _with_paths(paths: WorkspacePaths) -> Self {
+    pub fn with_paths(paths: WorkspacePaths) -> Self {
         Self { paths }
     }
 
@@ -68,14 +68,18 @@ impl Workspace for WorkspaceDir {
     where
         T: ConfigObject + 'static,
     {
-        self.check_if_initialized()?;
+        match self.paths.user_dir() {
+            Some(user_dir) if T::is_global() => {
+                fs::create_dir_all(user_dir).map_err(|e| Error::SavingConfig(e.into()))?
+            }
+            _ => self.check_if_initialized()?,
+        }
         let path = self.paths.config_path::<T>();
 
         info!("Saving object {}", path.to_str().unwrap());
         debug!("Saving object {:?}", &object);
 
         let serialized = serde_yaml::to_string(object)?;
-        debug!("Serialized: {}", serialized);
         fs::write(path, serialized).map_err(|e| Error::SavingConfig(e.into()))
     }
 
@@ -83,13 +87,14 @@ impl Workspace for WorkspaceDir {
     where
         T: ConfigObject + 'static,
     {
-        self.check_if_initialized()?;
+        if !(T::is_global() && self.paths.user_dir().is_some()) {
+            self.check_if_initialized()?;
+        }
         let path = self.paths.config_path::<T>();
 
         info!("Reading {}", path.to_str().unwrap());
         let serialized = fs::read_to_string(path).map_err(|e| Error::ReadingConfig(e.into()))?;
-        debug!("Serialized: {}", serialized);
-
+        // Only the masked Debug output is logged, the file holds plaintext secrets.
         let deserialized = serde_yaml::from_str::<T>(&serialized)?;
         debug!("Deserialized: {:?}", deserialized);
 
@@ -123,14 +128,16 @@ fn as_config_remove_error(e: io::Error) -> Error {
 pub(crate) mod tests {
     use super::*;
     use crate::workspace::ConnectionConfig;
+    use assert_fs::prelude::*;
     use assert_fs::TempDir;
+    use std::sync::{Arc, Mutex};
 
     pub fn make_temp_workspace(
     ) -> std::result::Result<(TempDir, WorkspacePaths, WorkspaceDir), Box<dyn std::error::Error>>
     {
         let temp_dir = assert_fs::TempDir::new()?;
-        let mock_paths = WorkspacePaths::_with_root(temp_dir.path());
-        let workspace = WorkspaceDir::_with_paths(mock_paths.clone());
+        let mock_paths = WorkspacePaths::with_root(temp_dir.path());
+        let workspace = WorkspaceDir::with_paths(mock_paths.clone());
         Ok((temp_dir, mock_paths, workspace))
     }
 
@@ -139,8 +146,11 @@ pub(crate) mod tests {
             host: "test_host".to_string(),
             login: "test_login".to_string(),
             password: "test_pass".to_string(),
+            password_command: None,
             permutation: "test_perm".to_string(),
             cookie: "test_cookie".to_string(),
+            secrets: None,
+            profile: None,
         }
     }
 
@@ -171,6 +181,71 @@ pub(crate) mod tests {
         assert!(fs::read_dir(mock_paths.baca_dir()).is_ok());
         temp_dir.close().unwrap();
     }
+
+    #[test]
+    fn init_inside_another_workspace_should_create_nested_one() {
+        let (temp_dir, _, workspace) = make_temp_workspace().unwrap();
+        workspace.initialize().unwrap();
+        let nested = temp_dir.child("zadanie");
+        nested.create_dir_all().unwrap();
+
+        let discovered = WorkspaceDir::with_paths(WorkspacePaths::discover(nested.path(), None));
+        assert!(matches!(
+            discovered.initialize(),
+            Err(Error::WorkspaceAlreadyInitialized)
+        ));
+
+        let nested_workspace = WorkspaceDir::with_paths(WorkspacePaths::with_root(nested.path()));
+        nested_workspace.initialize().unwrap();
+
+        assert!(nested.child(".baca").path().is_dir());
+        assert_eq!(
+            WorkspacePaths::discover(nested.path(), None).root(),
+            nested.path()
+        );
+        temp_dir.close().unwrap();
+    }
+
+    /// Collects the log output, without redacting the registered secrets.
+    #[derive(Clone, Default)]
+    struct CapturedLog(Arc<Mutex<Vec<u8>>>);
+
+    impl io::Write for CapturedLog {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn debug_log_should_not_contain_secrets() {
+        let (temp_dir, _, workspace) = make_temp_workspace().unwrap();
+        workspace.initialize().unwrap();
+        let mut config = make_baca();
+        config.password = "unlogged_password".to_string();
+        config.cookie = "unlogged_cookie".to_string();
+        let log = CapturedLog::default();
+        let writer = log.clone();
+        let subscriber = tracing_subscriber::FmtSubscriber::builder()
+            .with_max_level(tracing::Level::TRACE)
+            .with_writer(move || writer.clone())
+            .finish();
+
+        tracing::subscriber::with_default(subscriber, || {
+            workspace.save_config_object(&config).unwrap();
+            workspace.read_config_object::<ConnectionConfig>().unwrap();
+        });
+
+        let output = String::from_utf8(log.0.lock().unwrap().clone()).unwrap();
+        assert!(output.contains("Reading"), "{}", output);
+        assert!(!output.contains("unlogged_password"), "{}", output);
+        assert!(!output.contains("unlogged_cookie"), "{}", output);
+        temp_dir.close().unwrap();
+    }
     // todo: tests for removing and saving objects
 
     #[test]
@@ -1,33 +1,177 @@
 use crate::workspace::ConfigObject;
+use std::env;
 use std::path::{Path, PathBuf};
 
-// todo: walk up dir tree until found
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct WorkspacePaths {
     root_path: PathBuf,
+    user_dir: Option<PathBuf>,
 }
 
 impl WorkspacePaths {
+    /// Finds the workspace containing the current directory.
     pub fn new() -> Self {
-        Self {
-            root_path: Path::new(".").to_path_buf(),
-        }
+        Self::discover(&current_dir(), home_dir().as_deref()).with_user_dir(user_config_dir())
+    }
+
+    /// The current directory, without looking for a workspace in its parents.
+    pub fn current() -> Self {
+        Self::with_root(&current_dir()).with_user_dir(user_config_dir())
     }
 
-    pub(crate) fn _with_root(root_path: &Path) -> Self {
+    pub fn with_root(root_path: &Path) -> Self {
         Self {
             root_path: root_path.to_path_buf(),
+            user_dir: None,
+        }
+    }
+
+    /// Without a user directory, global config objects are kept in the workspace.
+    pub fn with_user_dir(self, user_dir: Option<PathBuf>) -> Self {
+        Self { user_dir, ..self }
+    }
+
+    /// Walks up from `start` to the first directory containing `.baca`, like git does for `.git`.
+    /// The search stops at `home` or the filesystem root, falling back to `start`.
+    pub fn discover(start: &Path, home: Option<&Path>) -> Self {
+        for dir in start.ancestors() {
+            let candidate = Self::with_root(dir);
+
+            if candidate.baca_dir().is_dir() {
+                return candidate;
+            }
+
+            if home.is_some_and(|home| dir == home) {
+                break;
+            }
         }
+
+        Self::with_root(start)
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root_path
+    }
+
+    pub fn user_dir(&self) -> Option<&Path> {
+        self.user_dir.as_deref()
     }
 
     pub fn baca_dir(&self) -> PathBuf {
         self.root_path.join(".baca")
     }
 
+    pub fn tests_dir(&self) -> PathBuf {
+        self.baca_dir().join("tests")
+    }
+
+    /// Copies of the submitted files, by submit id.
+    pub fn submissions_dir(&self) -> PathBuf {
+        self.baca_dir().join("submissions")
+    }
+
+    /// Raw requests and responses captured at trace level.
+    pub fn traffic_dir(&self) -> PathBuf {
+        self.baca_dir().join("traffic")
+    }
+
+    /// Passphrase remembered between runs, see [`Encryption`](super::Encryption).
+    /// Never kept in the workspace, so without the user directory it is not remembered.
+    pub fn session_path(&self) -> Option<PathBuf> {
+        self.user_dir.as_ref().map(|x| x.join("session"))
+    }
+
     pub fn config_path<T>(&self) -> PathBuf
     where
         T: ConfigObject,
     {
-        self.baca_dir().join(T::config_filename())
+        match &self.user_dir {
+            Some(user_dir) if T::is_global() => user_dir.join(T::config_filename()),
+            _ => self.baca_dir().join(T::config_filename()),
+        }
+    }
+}
+
+fn current_dir() -> PathBuf {
+    env::current_dir().unwrap_or_else(|_| PathBuf::from("."))
+}
+
+fn home_dir() -> Option<PathBuf> {
+    env::var_os("HOME")
+        .or_else(|| env::var_os("USERPROFILE"))
+        .filter(|x| !x.is_empty())
+        .map(PathBuf::from)
+}
+
+/// `$XDG_CONFIG_HOME/baca`, `~/.config/baca` by default, or `%APPDATA%\baca` on Windows.
+pub fn user_config_dir() -> Option<PathBuf> {
+    let xdg_config_home = env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .filter(|x| x.is_absolute());
+
+    if let Some(dir) = xdg_config_home {
+        return Some(dir.join("baca"));
+    }
+
+    if cfg!(windows) {
+        if let Some(dir) = env::var_os("APPDATA").filter(|x| !x.is_empty()) {
+            return Some(PathBuf::from(dir).join("baca"));
+        }
+    }
+
+    home_dir().map(|x| x.join(".config").join("baca"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert_fs::prelude::*;
+    use assert_fs::TempDir;
+
+    #[test]
+    fn workspace_should_be_found_in_parent_directory() {
+        let dir = TempDir::new().unwrap();
+        dir.child(".baca").create_dir_all().unwrap();
+        let nested = dir.child("zadanie/src");
+        nested.create_dir_all().unwrap();
+
+        let paths = WorkspacePaths::discover(nested.path(), None);
+
+        assert_eq!(paths.root(), dir.path());
+    }
+
+    #[test]
+    fn nearest_workspace_should_be_preferred() {
+        let dir = TempDir::new().unwrap();
+        dir.child(".baca").create_dir_all().unwrap();
+        dir.child("zadanie/.baca").create_dir_all().unwrap();
+        let nested = dir.child("zadanie/src");
+        nested.create_dir_all().unwrap();
+
+        let paths = WorkspacePaths::discover(nested.path(), None);
+
+        assert_eq!(paths.root(), dir.child("zadanie").path());
+    }
+
+    #[test]
+    fn search_should_stop_at_home() {
+        let dir = TempDir::new().unwrap();
+        dir.child(".baca").create_dir_all().unwrap();
+        let home = dir.child("home");
+        let nested = dir.child("home/zadanie");
+        nested.create_dir_all().unwrap();
+
+        let paths = WorkspacePaths::discover(nested.path(), Some(home.path()));
+
+        assert_eq!(paths.root(), nested.path());
+    }
+
+    #[test]
+    fn missing_workspace_should_fall_back_to_start() {
+        let dir = TempDir::new().unwrap();
+
+        let paths = WorkspacePaths::discover(dir.path(), Some(dir.path()));
+
+        assert_eq!(paths.root(), dir.path());
     }
 }
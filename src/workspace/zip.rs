@@ -1,28 +1,70 @@
 use crate::error;
+use crate::workspace::SourceFile;
 use std::fs::{read, File};
 use std::io::{Error, ErrorKind, Write};
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
-pub fn zip_file(path: &Path) -> Result<&Path, error::Error> {
-    zip_file_impl(path).map_err(|e| error::Error::Zipping(e.into()))
+pub fn zip_files(sources: &[SourceFile], output_dir: &Path) -> Result<PathBuf, error::Error> {
+    zip_files_impl(sources, output_dir).map_err(|e| error::Error::Zipping(e.into()))
 }
 
-fn zip_file_impl(path: &Path) -> Result<&Path, Error> {
-    let filename = path.file_name().unwrap().to_str().ok_or(ErrorKind::Other)?;
-    let path = path.to_str().ok_or(ErrorKind::Other)?;
-
-    println!("Zipping {}.", filename);
-    tracing::debug!("Relative path: {}.", path);
-
-    let source = read(path)?;
-    let buf = File::create("source.zip")?;
+fn zip_files_impl(sources: &[SourceFile], output_dir: &Path) -> Result<PathBuf, Error> {
+    let zip_path = output_dir.join("source.zip");
+    let buf = File::create(&zip_path)?;
     let mut zip = zip::ZipWriter::new(buf);
 
     let options =
         zip::write::FileOptions::default().compression_method(zip::CompressionMethod::DEFLATE);
-    zip.start_file(filename, options)?;
-    zip.write_all(source.as_ref())?;
+
+    for source in sources {
+        // Zip entries always use forward slashes.
+        let name = source
+            .name
+            .iter()
+            .map(|x| x.to_str().ok_or(ErrorKind::Other))
+            .collect::<Result<Vec<_>, _>>()?
+            .join("/");
+
+        println!("Zipping {}.", name);
+        tracing::debug!("Relative path: {:?}.", source.path);
+
+        let content = read(&source.path)?;
+        zip.start_file(name, options)?;
+        zip.write_all(content.as_ref())?;
+    }
+
     zip.finish()?;
+    Ok(zip_path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert_fs::prelude::*;
+    use std::io::Read;
+
+    #[test]
+    fn all_sources_should_be_zipped_with_their_names() {
+        let dir = assert_fs::TempDir::new().unwrap();
+        let main = dir.child("main.cpp");
+        main.write_str("int main() {}").unwrap();
+        let header = dir.child("include/matrix.h");
+        header.write_str("#pragma once").unwrap();
+        let sources = vec![
+            SourceFile::new(main.to_path_buf(), PathBuf::from("main.cpp")),
+            SourceFile::new(header.to_path_buf(), PathBuf::from("include/matrix.h")),
+        ];
+
+        let zip_path = zip_files(&sources, dir.path()).unwrap();
 
-    Ok(Path::new("source.zip"))
+        let mut archive = zip::ZipArchive::new(File::open(zip_path).unwrap()).unwrap();
+        assert_eq!(archive.len(), 2);
+        let mut content = String::new();
+        archive
+            .by_name("include/matrix.h")
+            .unwrap()
+            .read_to_string(&mut content)
+            .unwrap();
+        assert_eq!(content, "#pragma once");
+    }
 }